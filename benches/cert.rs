@@ -1,11 +1,14 @@
 use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use rustc_hash::FxHashMap;
+use std::collections::hash_map::Entry;
 
 use stride::examiner::Discord::Permissive;
 use stride::examiner::{Examiner, Record, Candidate};
 use stride::examiner::Outcome::Commit;
-use stride::suffix::{Suffix, AppendResult, CompleteResult};
+use stride::suffix::{Suffix, AppendResult, CompleteResult, TruncatedEntry};
 use uuid::Uuid;
 use stride::sortedvec::SortedVec;
+use stride::interner::{Interner, Sym};
 
 fn criterion_benchmark(c: &mut Criterion) {
     let (min_extent, max_extent) = (10_000, 20_000);
@@ -33,6 +36,7 @@ fn criterion_benchmark(c: &mut Criterion) {
                 writeset: writeset.clone(),
                 readvers: SortedVec::default(),
                 snapshot: *ver - 1,
+                ..Default::default()
             },
             ver: *ver,
         };
@@ -60,18 +64,16 @@ fn criterion_benchmark(c: &mut Criterion) {
 
                 assert_eq!(CompleteResult::Completed(ver), suffix.complete(ver));
 
-                if {
-                    let truncated = suffix.truncate(min_extent, max_extent);
-                    match truncated {
-                        None => false,
-                        Some(truncated_entries) => {
-                            for truncated_entry in truncated_entries {
-                                examiner.discard(truncated_entry);
-                            }
-                            true
+                let truncated = match suffix.truncate(min_extent, max_extent) {
+                    None => false,
+                    Some(truncated_entries) => {
+                        for truncated_entry in truncated_entries {
+                            examiner.discard(truncated_entry);
                         }
+                        true
                     }
-                } {
+                };
+                if truncated {
                     let range = suffix.range();
                     let span = (range.end - range.start) as usize;
                     assert!(span > 0 && span <= max_extent, "range {:?}", range);
@@ -101,18 +103,16 @@ fn criterion_benchmark(c: &mut Criterion) {
 
                 assert_eq!(CompleteResult::Completed(ver), suffix.complete(ver));
 
-                if {
-                    let truncated = suffix.truncate(min_extent, max_extent);
-                    match truncated {
-                        None => false,
-                        Some(truncated_entries) => {
-                            for truncated_entry in truncated_entries {
-                                examiner.discard(truncated_entry);
-                            }
-                            true
+                let truncated = match suffix.truncate(min_extent, max_extent) {
+                    None => false,
+                    Some(truncated_entries) => {
+                        for truncated_entry in truncated_entries {
+                            examiner.discard(truncated_entry);
                         }
+                        true
                     }
-                } {
+                };
+                if truncated {
                     let range = suffix.range();
                     let span = (range.end - range.start) as usize;
                     assert!(span > 0 && span <= max_extent, "range {:?}", range);
@@ -121,7 +121,312 @@ fn criterion_benchmark(c: &mut Criterion) {
             BatchSize::SmallInput,
         );
     });
+
+    // the records are reused across versions, so that the examiner's borrowing API is measured
+    // without the allocation of a fresh candidate each time
+    let setup_records = || {
+        (0..num_combos)
+            .map(|i| Record {
+                xid: Uuid::nil(),
+                readset: item_combos[i].clone(),
+                writeset: item_combos[(i + 1) % num_combos].clone(),
+                readvers: SortedVec::default(),
+                snapshot: 0,
+                ..Default::default()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    c.bench_function("cert_assess_record", |b| {
+        let mut suffix = Suffix::new(max_extent);
+        let mut examiner = Examiner::new();
+        let mut records = setup_records();
+        let mut ver: u64 = 1;
+        b.iter(|| {
+            let rec = &mut records[ver as usize % num_combos];
+            rec.snapshot = ver - 1;
+            let result = suffix.append(rec.readset.clone(), rec.writeset.clone(), ver);
+            assert_eq!(AppendResult::Appended, result);
+            assert_eq!(Some(ver + 1), suffix.hwm());
+
+            let outcome = examiner.assess_record(rec, ver);
+            assert_eq!(Commit {safepoint: ver - 1, discord: Permissive}, outcome);
+
+            assert_eq!(CompleteResult::Completed(ver), suffix.complete(ver));
+
+            let truncated = match suffix.truncate(min_extent, max_extent) {
+                None => false,
+                Some(truncated_entries) => {
+                    for truncated_entry in truncated_entries {
+                        examiner.discard(truncated_entry);
+                    }
+                    true
+                }
+            };
+            if truncated {
+                let range = suffix.range();
+                let span = (range.end - range.start) as usize;
+                assert!(span > 0 && span <= max_extent, "range {:?}", range);
+            }
+            ver += 1;
+        });
+    });
+
+    c.bench_function("cert_assess_interned", |b| {
+        let mut interner = Interner::new();
+        let mut suffix: Suffix<Sym> = Suffix::with_capacity(max_extent);
+        let mut examiner = Examiner::default();
+        let mut ver: u64 = 1;
+        b.iter_batched(
+            || setup_candidate(&mut ver),
+            |candidate| {
+                let rec = interner.intern_record(&candidate.rec);
+                let result = suffix.append(
+                    rec.readset.clone(),
+                    rec.writeset.clone(),
+                    candidate.ver,
+                );
+                assert_eq!(AppendResult::Appended, result);
+                assert_eq!(Some(candidate.ver + 1), suffix.hwm());
+
+                let ver = candidate.ver;
+                let outcome = examiner.assess(Candidate { rec, ver });
+                assert_eq!(Commit {safepoint: ver - 1, discord: Permissive}, outcome);
+
+                assert_eq!(CompleteResult::Completed(ver), suffix.complete(ver));
+
+                let truncated = match suffix.truncate(min_extent, max_extent) {
+                    None => false,
+                    Some(truncated_entries) => {
+                        for truncated_entry in truncated_entries {
+                            examiner.discard_interned(truncated_entry, &mut interner);
+                        }
+                        true
+                    }
+                };
+                if truncated {
+                    let range = suffix.range();
+                    let span = (range.end - range.start) as usize;
+                    assert!(span > 0 && span <= max_extent, "range {:?}", range);
+                }
+            },
+            BatchSize::SmallInput,
+        );
+    });
+
+    // as the fixtures certify: the interned record is borrowed by the examiner, then handed over to
+    // the suffix without cloning
+    c.bench_function("cert_assess_interned_record", |b| {
+        let mut interner = Interner::new();
+        let mut suffix: Suffix<Sym> = Suffix::with_capacity(max_extent);
+        let mut examiner = Examiner::default();
+        let mut records = setup_records();
+        let mut ver: u64 = 1;
+        b.iter(|| {
+            let rec = &mut records[ver as usize % num_combos];
+            rec.snapshot = ver - 1;
+            let rec = interner.intern_record(rec);
+            let outcome = examiner.assess_record(&rec, ver);
+            assert_eq!(Commit {safepoint: ver - 1, discord: Permissive}, outcome);
+
+            let result = suffix.append(rec.readset, rec.writeset, ver);
+            assert_eq!(AppendResult::Appended, result);
+            assert_eq!(Some(ver + 1), suffix.hwm());
+
+            assert_eq!(CompleteResult::Completed(ver), suffix.complete(ver));
+
+            let truncated = match suffix.truncate(min_extent, max_extent) {
+                None => false,
+                Some(truncated_entries) => {
+                    for truncated_entry in truncated_entries {
+                        examiner.discard_interned(truncated_entry, &mut interner);
+                    }
+                    true
+                }
+            };
+            if truncated {
+                let range = suffix.range();
+                let span = (range.end - range.start) as usize;
+                assert!(span > 0 && span <= max_extent, "range {:?}", range);
+            }
+            ver += 1;
+        });
+    });
+}
+
+// Steady-state certification of wider records over a key space that outgrows the caches,
+// truncating in bursts of a hundred thousand entries, so that the cost of discarding is measured
+// alongside that of assessing.
+fn truncation_benchmark(c: &mut Criterion) {
+    let (min_extent, max_extent) = (100_000, 200_000);
+    let num_items = 1_000_000;
+    let keys_per_set = 4;
+    let items = (0..num_items)
+        .map(|i| format!("item-{}", i))
+        .collect::<Vec<_>>();
+    let num_records = 250_007;
+    let setup_records = || {
+        (0..num_records)
+            .map(|i| {
+                let keyset = |offset: usize| {
+                    (0..keys_per_set)
+                        .map(|j| items[(i * 7_919 + offset + j * 13) % num_items].clone())
+                        .collect::<Vec<_>>()
+                };
+                Record {
+                    xid: Uuid::nil(),
+                    readset: keyset(0),
+                    writeset: keyset(5),
+                    readvers: SortedVec::default(),
+                    snapshot: 0,
+                    ..Default::default()
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    c.bench_function("cert_assess_truncating", |b| {
+        let mut suffix = Suffix::new(max_extent);
+        let mut examiner = Examiner::new();
+        let mut records = setup_records();
+        let mut ver: u64 = 1;
+        b.iter(|| {
+            let rec = &mut records[ver as usize % num_records];
+            rec.snapshot = ver - 1;
+            let outcome = examiner.assess_record(rec, ver);
+            assert!(outcome.is_commit());
+
+            let result = suffix.append(rec.readset.clone(), rec.writeset.clone(), ver);
+            assert_eq!(AppendResult::Appended, result);
+            assert_eq!(CompleteResult::Completed(ver), suffix.complete(ver));
+
+            if let Some(truncated_entries) = suffix.truncate(min_extent, max_extent) {
+                for truncated_entry in truncated_entries {
+                    examiner.discard(truncated_entry);
+                }
+            }
+            ver += 1;
+        });
+    });
+
+    // Before and after: the examiner's version-listed tables against maps probed for each key of
+    // each truncated entry, as the examiner's tables were, both learning and discarding the same
+    // records through the same truncating suffix.
+    let mut group = c.benchmark_group("cert_learn_truncating");
+    group.bench_function("probed", |b| {
+        let mut suffix = Suffix::new(max_extent);
+        let mut tables = ProbedTables::default();
+        let records = setup_records();
+        let mut ver: u64 = 1;
+        b.iter(|| {
+            let rec = &records[ver as usize % num_records];
+            tables.learn(rec, ver);
+
+            let result = suffix.append(rec.readset.clone(), rec.writeset.clone(), ver);
+            assert_eq!(AppendResult::Appended, result);
+            assert_eq!(CompleteResult::Completed(ver), suffix.complete(ver));
+
+            if let Some(truncated_entries) = suffix.truncate(min_extent, max_extent) {
+                for truncated_entry in truncated_entries {
+                    tables.discard(truncated_entry);
+                }
+            }
+            ver += 1;
+        });
+    });
+
+    group.bench_function("version_listed", |b| {
+        let mut suffix = Suffix::new(max_extent);
+        let mut examiner = Examiner::new();
+        let records = setup_records();
+        let mut ver: u64 = 1;
+        b.iter(|| {
+            let rec = &records[ver as usize % num_records];
+            examiner.learn_record(rec, ver);
+
+            let result = suffix.append(rec.readset.clone(), rec.writeset.clone(), ver);
+            assert_eq!(AppendResult::Appended, result);
+            assert_eq!(CompleteResult::Completed(ver), suffix.complete(ver));
+
+            if let Some(truncated_entries) = suffix.truncate(min_extent, max_extent) {
+                for truncated_entry in truncated_entries {
+                    examiner.discard(truncated_entry);
+                }
+            }
+            ver += 1;
+        });
+    });
+    group.finish();
+
+    c.bench_function("cert_assess_interned_truncating", |b| {
+        let mut interner = Interner::new();
+        let mut suffix: Suffix<Sym> = Suffix::with_capacity(max_extent);
+        let mut examiner = Examiner::default();
+        let mut records = setup_records();
+        let mut ver: u64 = 1;
+        b.iter(|| {
+            let rec = &mut records[ver as usize % num_records];
+            rec.snapshot = ver - 1;
+            let rec = interner.intern_record(rec);
+            let outcome = examiner.assess_record(&rec, ver);
+            assert!(outcome.is_commit());
+
+            let result = suffix.append(rec.readset, rec.writeset, ver);
+            assert_eq!(AppendResult::Appended, result);
+            assert_eq!(CompleteResult::Completed(ver), suffix.complete(ver));
+
+            if let Some(truncated_entries) = suffix.truncate(min_extent, max_extent) {
+                for truncated_entry in truncated_entries {
+                    examiner.discard_interned(truncated_entry, &mut interner);
+                }
+            }
+            ver += 1;
+        });
+    });
+}
+
+// the examiner's read and write tables as they were before being version-listed: a discard looks
+// up each key of the truncated entry, removing it if it was last learned at the entry's version
+#[derive(Default)]
+struct ProbedTables {
+    reads: FxHashMap<String, u64>,
+    writes: FxHashMap<String, u64>,
+}
+
+impl ProbedTables {
+    fn learn(&mut self, rec: &Record, ver: u64) {
+        for read in &rec.readset {
+            Self::insert_item(&mut self.reads, read, ver);
+        }
+        for write in &rec.writeset {
+            Self::insert_item(&mut self.writes, write, ver);
+        }
+    }
+
+    fn insert_item(items: &mut FxHashMap<String, u64>, key: &str, ver: u64) {
+        match items.get_mut(key) {
+            Some(existing) => *existing = ver,
+            None => {
+                items.insert(key.to_string(), ver);
+            }
+        }
+    }
+
+    fn discard(&mut self, entry: TruncatedEntry) {
+        Self::remove_items(&mut self.reads, entry.readset, entry.ver);
+        Self::remove_items(&mut self.writes, entry.writeset, entry.ver);
+    }
+
+    fn remove_items(items: &mut FxHashMap<String, u64>, keys: Vec<String>, ver: u64) {
+        for key in keys {
+            if let Entry::Occupied(existing) = items.entry(key) {
+                if *existing.get() == ver {
+                    existing.remove();
+                }
+            }
+        }
+    }
 }
 
-criterion_group!(benches, criterion_benchmark);
+criterion_group!(benches, criterion_benchmark, truncation_benchmark);
 criterion_main!(benches);
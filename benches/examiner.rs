@@ -18,6 +18,7 @@ fn criterion_benchmark(c: &mut Criterion) {
                         writeset: vec!["y".into()],
                         readvers: SortedVec::default(),
                         snapshot: ver - 1,
+                        ..Default::default()
                     },
                     ver,
                 };
@@ -43,6 +44,7 @@ fn criterion_benchmark(c: &mut Criterion) {
                         writeset: vec!["y".into()],
                         readvers: SortedVec::default(),
                         snapshot: ver - 1,
+                        ..Default::default()
                     },
                     ver,
                 };
@@ -57,6 +59,35 @@ fn criterion_benchmark(c: &mut Criterion) {
             BatchSize::SmallInput,
         )
     });
+
+    // the borrowing variants reuse one record throughout, cloning keys only when first learned
+    let rec = Record {
+        xid: Uuid::nil(),
+        readset: vec!["x".into()],
+        writeset: vec!["y".into()],
+        readvers: SortedVec::default(),
+        snapshot: 0,
+        ..Default::default()
+    };
+
+    c.bench_function("examiner_learn_record", |b| {
+        let mut examiner = Examiner::new();
+        let mut ver = 1;
+        b.iter(|| {
+            examiner.learn_record(&rec, ver);
+            ver += 1;
+        })
+    });
+
+    c.bench_function("examiner_assess_record", |b| {
+        let mut examiner = Examiner::new();
+        let mut ver = 1;
+        b.iter(|| {
+            let outcome = examiner.assess_record(&rec, ver);
+            assert_eq!(Commit {safepoint: ver - 1, discord: Discord::Permissive}, outcome);
+            ver += 1;
+        })
+    });
 }
 
 criterion_group!(benches, criterion_benchmark);
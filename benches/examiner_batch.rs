@@ -0,0 +1,69 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use uuid::Uuid;
+
+use stride::examiner::{Candidate, Examiner, Record};
+use stride::sortedvec::SortedVec;
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let batch_size = 100;
+    let num_items = 10_000;
+    let items = (0..num_items)
+        .map(|i| format!("item-{}", i))
+        .collect::<Vec<_>>();
+
+    let setup_batch = |ver: &mut u64| {
+        (0..batch_size)
+            .map(|_| {
+                let candidate = Candidate {
+                    rec: Record {
+                        xid: Uuid::nil(),
+                        readset: vec![items[*ver as usize % num_items].clone()],
+                        writeset: vec![items[(*ver + 1) as usize % num_items].clone()],
+                        readvers: SortedVec::default(),
+                        snapshot: *ver - 1,
+                        ..Default::default()
+                    },
+                    ver: *ver,
+                };
+                *ver += 1;
+                candidate
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut group = c.benchmark_group("examiner_assess_100");
+    group.throughput(Throughput::Elements(batch_size));
+
+    // both take the run by reference, so that neither pays for dropping it
+    group.bench_function("sequential", |b| {
+        let mut examiner = Examiner::new();
+        let mut ver = 1;
+        b.iter_batched_ref(
+            || setup_batch(&mut ver),
+            |candidates| {
+                for candidate in candidates.iter() {
+                    assert!(examiner.assess_record(&candidate.rec, candidate.ver).is_commit());
+                }
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.bench_function("batch", |b| {
+        let mut examiner = Examiner::new();
+        let mut ver = 1;
+        b.iter_batched_ref(
+            || setup_batch(&mut ver),
+            |candidates| {
+                let outcomes = examiner.assess_batch(candidates);
+                assert!(outcomes.iter().all(|outcome| outcome.is_commit()));
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
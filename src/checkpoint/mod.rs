@@ -0,0 +1,211 @@
+use std::fmt::{Display, Formatter};
+
+// The version of the checkpoint format written by this build. A reader accepts this version only:
+// there is no backward read, so a checkpoint written by another version must be rebuilt from the
+// log rather than restored. The version is bumped once per release that changes the format.
+pub const FORMAT_VERSION: u8 = 1;
+
+// a checkpoint that could not be restored
+#[derive(Debug, PartialEq, Clone)]
+pub enum CheckpointError {
+    BadMagic,
+    UnsupportedVersion(u8),
+    Truncated,
+    InvalidTag(u8),
+    InvalidUtf8,
+    Overflow,
+    TrailingBytes(usize),
+}
+
+impl Display for CheckpointError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CheckpointError::BadMagic => write!(f, "not a checkpoint of the expected kind"),
+            CheckpointError::UnsupportedVersion(version) => {
+                write!(f, "unsupported checkpoint version {}", version)
+            }
+            CheckpointError::Truncated => write!(f, "truncated checkpoint"),
+            CheckpointError::InvalidTag(tag) => write!(f, "invalid tag {}", tag),
+            CheckpointError::InvalidUtf8 => write!(f, "invalid UTF-8 in key"),
+            CheckpointError::Overflow => write!(f, "integer overflow"),
+            CheckpointError::TrailingBytes(len) => write!(f, "{} trailing bytes", len),
+        }
+    }
+}
+
+impl std::error::Error for CheckpointError {}
+
+// Accumulates a checkpoint: a 4-byte magic identifying its kind, the format version, then the
+// body. Integers are written as LEB128 varints, so small versions and lengths take a byte or two.
+pub struct Writer {
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    pub fn new(magic: &[u8; 4]) -> Self {
+        let mut buf = Vec::with_capacity(64);
+        buf.extend_from_slice(magic);
+        buf.push(FORMAT_VERSION);
+        Self { buf }
+    }
+
+    pub fn u8(&mut self, val: u8) {
+        self.buf.push(val);
+    }
+
+    pub fn varint(&mut self, mut val: u64) {
+        while val >= 0x80 {
+            self.buf.push(val as u8 | 0x80);
+            val >>= 7;
+        }
+        self.buf.push(val as u8);
+    }
+
+    pub fn length(&mut self, len: usize) {
+        self.varint(len as u64);
+    }
+
+    pub fn bytes(&mut self, bytes: &[u8]) {
+        self.length(bytes.len());
+        self.buf.extend_from_slice(bytes);
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        self.buf
+    }
+}
+
+pub struct Reader<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(buf: &'a [u8], magic: &[u8; 4]) -> Result<Self, CheckpointError> {
+        if buf.len() < magic.len() {
+            return Err(CheckpointError::Truncated);
+        }
+        if &buf[..magic.len()] != magic {
+            return Err(CheckpointError::BadMagic);
+        }
+        let mut reader = Self { buf: &buf[magic.len()..] };
+        match reader.u8()? {
+            FORMAT_VERSION => Ok(reader),
+            version => Err(CheckpointError::UnsupportedVersion(version)),
+        }
+    }
+
+    pub fn u8(&mut self) -> Result<u8, CheckpointError> {
+        let (&val, rest) = self.buf.split_first().ok_or(CheckpointError::Truncated)?;
+        self.buf = rest;
+        Ok(val)
+    }
+
+    pub fn varint(&mut self) -> Result<u64, CheckpointError> {
+        let mut val = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.u8()?;
+            val |= ((byte & 0x7F) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(val);
+            }
+        }
+        Err(CheckpointError::Overflow)
+    }
+
+    // a length prefix, which cannot exceed the bytes remaining since every element takes at least one
+    pub fn length(&mut self) -> Result<usize, CheckpointError> {
+        let len = self.varint()?;
+        if len > self.buf.len() as u64 {
+            return Err(CheckpointError::Truncated);
+        }
+        Ok(len as usize)
+    }
+
+    pub fn bytes(&mut self) -> Result<&'a [u8], CheckpointError> {
+        let len = self.length()?;
+        let (bytes, rest) = self.buf.split_at(len);
+        self.buf = rest;
+        Ok(bytes)
+    }
+
+    pub fn finish(self) -> Result<(), CheckpointError> {
+        match self.buf.len() {
+            0 => Ok(()),
+            len => Err(CheckpointError::TrailingBytes(len)),
+        }
+    }
+}
+
+// a key (or other value) that can be written to a checkpoint
+pub trait Codec: Sized {
+    fn encode(&self, writer: &mut Writer);
+
+    fn decode(reader: &mut Reader) -> Result<Self, CheckpointError>;
+}
+
+impl Codec for u64 {
+    fn encode(&self, writer: &mut Writer) {
+        writer.varint(*self);
+    }
+
+    fn decode(reader: &mut Reader) -> Result<Self, CheckpointError> {
+        reader.varint()
+    }
+}
+
+impl Codec for u32 {
+    fn encode(&self, writer: &mut Writer) {
+        writer.varint(*self as u64);
+    }
+
+    fn decode(reader: &mut Reader) -> Result<Self, CheckpointError> {
+        let val = reader.varint()?;
+        if val > u32::MAX as u64 {
+            return Err(CheckpointError::Overflow);
+        }
+        Ok(val as u32)
+    }
+}
+
+impl Codec for String {
+    fn encode(&self, writer: &mut Writer) {
+        writer.bytes(self.as_bytes());
+    }
+
+    fn decode(reader: &mut Reader) -> Result<Self, CheckpointError> {
+        let bytes = reader.bytes()?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| CheckpointError::InvalidUtf8)
+    }
+}
+
+impl<A: Codec, B: Codec> Codec for (A, B) {
+    fn encode(&self, writer: &mut Writer) {
+        self.0.encode(writer);
+        self.1.encode(writer);
+    }
+
+    fn decode(reader: &mut Reader) -> Result<Self, CheckpointError> {
+        Ok((A::decode(reader)?, B::decode(reader)?))
+    }
+}
+
+impl<T: Codec> Codec for Vec<T> {
+    fn encode(&self, writer: &mut Writer) {
+        writer.length(self.len());
+        for item in self {
+            item.encode(writer);
+        }
+    }
+
+    fn decode(reader: &mut Reader) -> Result<Self, CheckpointError> {
+        let len = reader.length()?;
+        let mut items = Vec::with_capacity(len);
+        for _ in 0..len {
+            items.push(T::decode(reader)?);
+        }
+        Ok(items)
+    }
+}
+
+#[cfg(test)]
+mod tests;
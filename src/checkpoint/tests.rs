@@ -0,0 +1,87 @@
+use crate::checkpoint::{CheckpointError, Codec, Reader, Writer, FORMAT_VERSION};
+
+const MAGIC: &[u8; 4] = b"TEST";
+
+fn round_trip<T: Codec + PartialEq + std::fmt::Debug>(val: T) -> Vec<u8> {
+    let mut writer = Writer::new(MAGIC);
+    val.encode(&mut writer);
+    let bytes = writer.finish();
+    let mut reader = Reader::new(&bytes, MAGIC).unwrap();
+    assert_eq!(val, T::decode(&mut reader).unwrap());
+    reader.finish().unwrap();
+    bytes
+}
+
+#[test]
+fn varint_round_trip() {
+    for &(val, len) in &[(0u64, 1), (1, 1), (127, 1), (128, 2), (16_383, 2), (16_384, 3), (u64::MAX, 10)] {
+        let bytes = round_trip(val);
+        assert_eq!(MAGIC.len() + 1 + len, bytes.len(), "{}", val);
+    }
+    round_trip(u32::MAX);
+}
+
+#[test]
+fn keys_round_trip() {
+    round_trip(String::new());
+    round_trip(String::from("naïve"));
+    round_trip((7u64, String::from("x")));
+    round_trip(vec![String::from("x"), String::from("y")]);
+    round_trip(Vec::<u64>::new());
+}
+
+#[test]
+fn header_errors() {
+    assert_eq!(Some(CheckpointError::Truncated), Reader::new(b"TES", MAGIC).err());
+    assert_eq!(Some(CheckpointError::BadMagic), Reader::new(b"BEST\x01", MAGIC).err());
+    assert_eq!(Some(CheckpointError::Truncated), Reader::new(b"TEST", MAGIC).err());
+    let mut future = MAGIC.to_vec();
+    future.push(FORMAT_VERSION + 1);
+    assert_eq!(
+        Some(CheckpointError::UnsupportedVersion(FORMAT_VERSION + 1)),
+        Reader::new(&future, MAGIC).err()
+    );
+    // nor is an earlier version read
+    let mut past = MAGIC.to_vec();
+    past.push(FORMAT_VERSION - 1);
+    assert_eq!(
+        Some(CheckpointError::UnsupportedVersion(FORMAT_VERSION - 1)),
+        Reader::new(&past, MAGIC).err()
+    );
+}
+
+fn body(bytes: &[u8]) -> Vec<u8> {
+    let mut checkpoint = MAGIC.to_vec();
+    checkpoint.push(FORMAT_VERSION);
+    checkpoint.extend_from_slice(bytes);
+    checkpoint
+}
+
+#[test]
+fn body_errors() {
+    let checkpoint = body(&[0x80]);
+    let mut reader = Reader::new(&checkpoint, MAGIC).unwrap();
+    assert_eq!(Err(CheckpointError::Truncated), u64::decode(&mut reader));
+
+    let checkpoint = body(&[0xFF; 11]);
+    let mut reader = Reader::new(&checkpoint, MAGIC).unwrap();
+    assert_eq!(Err(CheckpointError::Overflow), u64::decode(&mut reader));
+
+    let checkpoint = body(&[0x80, 0x80, 0x80, 0x80, 0x10]);
+    let mut reader = Reader::new(&checkpoint, MAGIC).unwrap();
+    assert_eq!(Err(CheckpointError::Overflow), u32::decode(&mut reader));
+
+    // a length beyond the remaining bytes
+    let checkpoint = body(&[3, b'x']);
+    let mut reader = Reader::new(&checkpoint, MAGIC).unwrap();
+    assert_eq!(Err(CheckpointError::Truncated), String::decode(&mut reader));
+
+    let checkpoint = body(&[2, 0xC3, 0x28]);
+    let mut reader = Reader::new(&checkpoint, MAGIC).unwrap();
+    assert_eq!(Err(CheckpointError::InvalidUtf8), String::decode(&mut reader));
+
+    let checkpoint = body(&[1, 2, 3]);
+    let mut reader = Reader::new(&checkpoint, MAGIC).unwrap();
+    assert_eq!(Ok(1), u64::decode(&mut reader));
+    assert_eq!(Err(CheckpointError::TrailingBytes(2)), reader.finish());
+}
@@ -0,0 +1,214 @@
+use crate::examiner::Key;
+use rustc_hash::FxHashMap;
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::fmt::{Debug, Formatter};
+use std::iter::FromIterator;
+
+// A table of the last version of each key, from which every key up to a version may be discarded
+// without looking the keys up.
+//
+// Each key maps to a slot, stamped with the key's version, and each slot is listed against the
+// version it was stamped with. Discarding through a version walks the lists up to it, vacating
+// the slots whose stamps it covers; a slot stamped again since is listed again, under its later
+// version. A vacated slot stays mapped to its key, so that learning the key again neither hashes
+// it into the map anew nor clones it; the keys of vacant slots are only unmapped once they
+// outnumber the occupied ones, so that sweeping the map is amortized over the discards that
+// vacated them. Likewise, listings whose slots were stamped again are pruned once they outnumber
+// the occupied slots.
+pub struct KeyTable<K> {
+    slots: FxHashMap<K, u32>,
+    stamps: Vec<u64>,                  // the version of each slot's key; 0 if vacant
+    listed: VecDeque<(u64, u32)>, // each slot against the version it was stamped with, in version order
+    unmapped: Vec<u32>, // slots free for reuse by a new key
+    occupied: usize,
+}
+
+// the listings beyond twice the occupied slots that are tolerated before pruning
+const PRUNE_SLACK: usize = 1024;
+
+impl<K> KeyTable<K> {
+    pub fn new() -> Self {
+        KeyTable {
+            slots: FxHashMap::default(),
+            stamps: vec![],
+            listed: VecDeque::new(),
+            unmapped: vec![],
+            occupied: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.occupied
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.occupied == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &u64)> {
+        let stamps = &self.stamps;
+        self.slots.iter().filter_map(move |(key, &slot)| match &stamps[slot as usize] {
+            0 => None,
+            ver => Some((key, ver)),
+        })
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &u64> {
+        self.iter().map(|(_, ver)| ver)
+    }
+
+    // vacates every slot stamped with a version up to and including the given one
+    pub fn discard_through(&mut self, ver: u64) {
+        while let Some(&(listed_ver, slot)) = self.listed.front() {
+            if listed_ver > ver {
+                break;
+            }
+            self.listed.pop_front();
+            let stamp = &mut self.stamps[slot as usize];
+            if *stamp != 0 && *stamp <= ver {
+                *stamp = 0;
+                self.occupied -= 1;
+            }
+        }
+        if self.slots.len() > 2 * self.occupied {
+            self.sweep();
+        }
+    }
+
+    // unmaps the keys of vacant slots, freeing the slots for reuse
+    fn sweep(&mut self) {
+        let (stamps, unmapped) = (&self.stamps, &mut self.unmapped);
+        self.slots.retain(|_, &mut slot| {
+            let occupied = stamps[slot as usize] != 0;
+            if !occupied {
+                unmapped.push(slot);
+            }
+            occupied
+        });
+    }
+
+    // drops the listings of slots since stamped with a later version
+    fn prune(&mut self) {
+        let stamps = &self.stamps;
+        self.listed.retain(|&(listed_ver, slot)| stamps[slot as usize] == listed_ver);
+    }
+
+    fn stamp(&mut self, slot: u32, ver: u64) -> Option<u64> {
+        debug_assert!(ver > 0, "invalid version");
+        let replaced = std::mem::replace(&mut self.stamps[slot as usize], ver);
+        if replaced == 0 {
+            self.occupied += 1;
+        }
+        match self.listed.back() {
+            Some(&(listed_ver, _)) if listed_ver > ver => {
+                let index = self.listed.partition_point(|&(listed_ver, _)| listed_ver <= ver);
+                self.listed.insert(index, (ver, slot));
+            }
+            _ => self.listed.push_back((ver, slot)),
+        }
+        if self.listed.len() > 2 * self.occupied + PRUNE_SLACK {
+            self.prune();
+        }
+        match replaced {
+            0 => None,
+            replaced => Some(replaced),
+        }
+    }
+
+    fn unmapped_slot(&mut self) -> u32 {
+        self.unmapped.pop().unwrap_or_else(|| {
+            self.stamps.push(0);
+            u32::try_from(self.stamps.len() - 1).expect("too many slots")
+        })
+    }
+}
+
+impl<K: Key> KeyTable<K> {
+    // reserves room for the given number of keys beyond those mapped
+    pub fn reserve(&mut self, additional: usize) {
+        self.slots.reserve(additional);
+        self.stamps.reserve(additional);
+        self.listed.reserve(additional);
+    }
+
+    pub fn get(&self, key: &K) -> Option<&u64> {
+        match self.slots.get(key) {
+            Some(&slot) if self.stamps[slot as usize] != 0 => Some(&self.stamps[slot as usize]),
+            _ => None,
+        }
+    }
+
+    // sets the key's version, cloning the key only if it is not mapped to a slot; returns the
+    // version it replaced
+    pub fn insert(&mut self, key: &K, ver: u64) -> Option<u64> {
+        let slot = match self.slots.get(key) {
+            Some(&slot) => slot,
+            None => {
+                let slot = self.unmapped_slot();
+                self.slots.insert(key.clone(), slot);
+                slot
+            }
+        };
+        self.stamp(slot, ver)
+    }
+
+    // vacates the key's slot, leaving the key mapped until swept; returns the version it held
+    pub fn remove(&mut self, key: &K) -> Option<u64> {
+        let slot = *self.slots.get(key)?;
+        match std::mem::replace(&mut self.stamps[slot as usize], 0) {
+            0 => None,
+            removed => {
+                self.occupied -= 1;
+                Some(removed)
+            }
+        }
+    }
+
+    pub fn insert_owned(&mut self, key: K, ver: u64) -> Option<u64> {
+        let slot = match self.slots.get(&key) {
+            Some(&slot) => slot,
+            None => {
+                let slot = self.unmapped_slot();
+                self.slots.insert(key, slot);
+                slot
+            }
+        };
+        self.stamp(slot, ver)
+    }
+}
+
+impl<K: Key> FromIterator<(K, u64)> for KeyTable<K> {
+    fn from_iter<I: IntoIterator<Item = (K, u64)>>(iter: I) -> Self {
+        // stamped in version order, so that each slot is listed at the back
+        let mut items = iter.into_iter().collect::<Vec<_>>();
+        items.sort_unstable_by_key(|&(_, ver)| ver);
+        let mut table = Self::new();
+        table.slots.reserve(items.len());
+        for (key, ver) in items {
+            table.insert_owned(key, ver);
+        }
+        table
+    }
+}
+
+impl<K: Key> PartialEq for KeyTable<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(key, ver)| other.get(key) == Some(ver))
+    }
+}
+
+impl<K: Debug> Debug for KeyTable<K> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K> Default for KeyTable<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,84 @@
+use crate::examiner::keytable::KeyTable;
+
+fn key(name: &str) -> String {
+    name.into()
+}
+
+#[test]
+fn insert_get_discard() {
+    let mut table = KeyTable::new();
+    assert!(table.is_empty());
+    assert_eq!(None, table.insert(&key("x"), 1));
+    assert_eq!(None, table.insert(&key("y"), 1));
+    assert_eq!(Some(1), table.insert(&key("x"), 2));
+    assert_eq!(2, table.len());
+    assert_eq!(Some(&2), table.get(&key("x")));
+    assert_eq!(Some(&1), table.get(&key("y")));
+
+    // 'x' was stamped again at version 2, so it outlives the discard of version 1
+    table.discard_through(1);
+    assert_eq!(1, table.len());
+    assert_eq!(Some(&2), table.get(&key("x")));
+    assert_eq!(None, table.get(&key("y")));
+    assert_eq!(vec![(&key("x"), &2)], table.iter().collect::<Vec<_>>());
+
+    table.discard_through(2);
+    assert!(table.is_empty());
+    assert_eq!(None, table.get(&key("x")));
+}
+
+#[test]
+fn reuse_vacant_slots() {
+    let mut table = KeyTable::new();
+    for ver in 1..=4 {
+        table.insert(&key("x"), ver);
+        table.insert(&format!("y-{}", ver), ver);
+        table.discard_through(ver - 1);
+        assert_eq!(2, table.len());
+    }
+    // the vacated keys are unmapped once they outnumber the occupied ones, freeing their slots
+    assert_eq!(2, table.slots.len());
+    assert_eq!(Some(&4), table.get(&key("x")));
+    assert_eq!(Some(&4), table.get(&key("y-4")));
+    assert_eq!(None, table.get(&key("y-3")));
+    let slots = table.stamps.len();
+    table.insert(&key("z"), 5);
+    assert_eq!(slots, table.stamps.len());
+    assert_eq!(Some(&5), table.get(&key("z")));
+}
+
+#[test]
+fn prune_restamped_listings() {
+    let mut table = KeyTable::new();
+    for ver in 1..=10_000 {
+        table.insert(&key("x"), ver);
+    }
+    assert_eq!(1, table.len());
+    assert!(table.listed.len() <= 2 + super::PRUNE_SLACK, "listings: {}", table.listed.len());
+
+    table.discard_through(9_999);
+    assert_eq!(Some(&10_000), table.get(&key("x")));
+    table.discard_through(10_000);
+    assert!(table.is_empty());
+}
+
+#[test]
+fn collect_out_of_order() {
+    let table = vec![(key("x"), 3), (key("y"), 1), (key("z"), 2)]
+        .into_iter()
+        .collect::<KeyTable<_>>();
+    assert_eq!(3, table.len());
+
+    let mut restamped = KeyTable::new();
+    restamped.insert(&key("z"), 2);
+    restamped.insert(&key("y"), 1);
+    restamped.insert(&key("x"), 3);
+    assert_eq!(table, restamped);
+
+    restamped.discard_through(1);
+    assert_ne!(table, restamped);
+    assert_eq!(None, restamped.get(&key("y")));
+    assert_eq!(Some(&2), restamped.get(&key("z")));
+    restamped.discard_through(2);
+    assert_eq!(vec![(&key("x"), &3)], restamped.iter().collect::<Vec<_>>());
+}
@@ -1,17 +1,95 @@
-use crate::examiner::AbortReason::{Antidependency, Staleness};
+use crate::examiner::AbortReason::{Antidependency, Phantom, Staleness, WriteConflict};
 use crate::examiner::Discord::{Assertive, Permissive};
+use crate::examiner::Isolation::{Serializable, Snapshot};
+use crate::checkpoint::{CheckpointError, Codec, Reader, Writer};
 use crate::examiner::Outcome::{Abort, Commit};
-use crate::suffix::TruncatedEntry;
+use crate::examiner::keytable::KeyTable;
+use crate::examiner::sharded::{Slice, Verdict};
+use crate::interner::{Interner, InternerError, Sym};
+use crate::suffix::{Suffix, TruncatedEntry};
 use rustc_hash::FxHashMap;
 use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, VecDeque};
+use std::convert::TryFrom;
+use std::fmt::{Debug, Display, Formatter};
+use std::hash::Hash;
+use std::ops::Bound;
 use uuid::Uuid;
 use crate::sortedvec::SortedVec;
 
+mod keytable;
+pub mod sharded;
+
+// an item key; any hashable, ordered and cloneable type will do
+pub trait Key: Hash + Eq + Ord + Clone + Debug {}
+
+impl<T: Hash + Eq + Ord + Clone + Debug> Key for T {}
+
+// a key within a hierarchy, e.g., a table and its rows; a coarse key covers every key beneath it
+pub trait Hierarchical: Key {
+    fn parent(&self) -> Option<Self>;
+}
+
+// '/'-separated paths, e.g., 'table/row'
+impl Hierarchical for String {
+    fn parent(&self) -> Option<Self> {
+        self.rfind('/').map(|separator| self[..separator].into())
+    }
+}
+
 #[derive(Debug)]
-pub struct Examiner {
-    reads: FxHashMap<String, u64>,
-    writes: FxHashMap<String, u64>,
+pub struct Examiner<K = String> {
+    reads: KeyTable<K>,
+    writes: KeyTable<K>,
+    ordered_writes: Option<BTreeMap<K, u64>>, // mirrors 'writes' for scanning; built on first scan
+    scans: VecDeque<(u64, Scan<K>)>,          // learned scans, in version order
     base: u64,
+    isolation: Isolation,
+    outcomes: OutcomeCounts,
+    discards: u64,
+    max_keys: Option<usize>, // the cap on tracked keys, beyond which the base is forced forward
+    forced_base: u64,        // the last forced base; entries below it were forgotten
+    forced_advances: u64,
+    parent: Option<fn(&K) -> Option<K>>, // set if keys are hierarchical
+    read_intents: FxHashMap<K, u64>,     // the last read beneath each coarse key
+    write_intents: FxHashMap<K, u64>,    // the last write beneath each coarse key
+    commutes: KeyTable<K>,               // the last commutative write of each key
+    refresh_hints: bool,
+    digest: Digest,
+    provisional: VecDeque<Provisional<K>>, // votes awaiting their combined outcome, in version order
+    precise: bool,
+    uncertain: VecDeque<u64>, // the learned versions that aborted permissively, in version order
+    unlearned: VecDeque<u64>, // the versions that aborted assertively and were not learned, in version order
+}
+
+// a partition's slice of a candidate spanning partitions, learned pending the combined outcome,
+// along with the versions that its keys, and the intents on their coarser keys, replaced
+#[derive(Debug)]
+struct Provisional<K> {
+    ver: u64,
+    reads: Vec<(K, Option<u64>)>,
+    writes: Vec<(K, Option<u64>)>,
+    commutes: Vec<(K, Option<u64>)>,
+    read_intents: Vec<(K, Option<u64>)>,
+    write_intents: Vec<(K, Option<u64>)>,
+}
+
+// the isolation level that the examiner certifies candidates to
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
+pub enum Isolation {
+    #[default]
+    Serializable, // abort on antidependency, i.e., a stale read or a phantom
+    Snapshot,     // abort on a lost update only, admitting write skew
+}
+
+// how the examiner learned a candidate, as recorded in the suffix it was appended to, so that an
+// examiner rebuilt from the suffix learns it alike
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
+pub enum Learning {
+    #[default]
+    Certain,   // learned as committed, or as any abort unless in precise mode
+    Uncertain, // learned, having aborted permissively in precise mode
+    Unlearned, // not learned, having aborted assertively in precise mode
 }
 
 #[derive(PartialEq, Debug, Clone)]
@@ -29,9 +107,45 @@ pub enum Outcome {
     Abort {
         reason: AbortReason,
         discord: Discord,
+        refresh: Option<Refresh>, // none unless the examiner gives refresh hints
     },
 }
 
+// what a cohort should catch up on before retrying an aborted candidate
+#[derive(PartialEq, Debug, Clone)]
+pub struct Refresh {
+    pub snapshot: u64,     // the minimum snapshot from which the candidate may succeed
+    pub reads: Vec<usize>, // the positions in the readset of the keys to re-read
+    pub scans: Vec<usize>, // the positions of the scans to repeat
+}
+
+impl Refresh {
+    fn new<K: Key>(rec: &Record<K>, detail: &AbortDetail<K>) -> Self {
+        match detail {
+            // the examiner no longer knows which reads are stale; all of them may be
+            AbortDetail::Staleness { snapshot: _, base } => Refresh {
+                snapshot: *base - 1,
+                reads: (0..rec.readset.len()).collect(),
+                scans: (0..rec.scans.len()).collect(),
+            },
+            AbortDetail::Antidependency { conflicts } | AbortDetail::WriteConflict { conflicts } => {
+                let conflicting = |key: &K| conflicts.iter().any(|(conflict_key, _)| conflict_key == key);
+                Refresh {
+                    snapshot: conflicts.iter().map(|&(_, ver)| ver).max().unwrap(),
+                    reads: (0..rec.readset.len())
+                        .filter(|&read_index| conflicting(&rec.readset[read_index]))
+                        .collect(),
+                    scans: (0..rec.scans.len())
+                        .filter(|&scan_index| {
+                            conflicts.iter().any(|(conflict_key, _)| rec.scans[scan_index].contains(conflict_key))
+                        })
+                        .collect(),
+                }
+            }
+        }
+    }
+}
+
 impl Outcome {
     // pub fn as_commit(&self) -> Option<(u64, &Discord)> {
     //     match self {
@@ -40,6 +154,45 @@ impl Outcome {
     //     }
     // }
 
+    // Combines the votes of the partitions that a candidate spans, given in partition order, into
+    // the candidate's outcome, identically wherever they are combined. The candidate commits only
+    // if every partition voted to; the combined safepoint is the highest of the partitions',
+    // which is only comparable where the partitions share a version space, so each partition
+    // installs the candidate behind the safepoint of its own vote. An abort takes the reason of the
+    // first partition to vote to abort, but not its refresh hint, which refers to that partition's
+    // slice alone. A commit is assertive only if every vote was, an abort if any vote to abort was.
+    pub fn combine(votes: &[Outcome]) -> Outcome {
+        assert!(!votes.is_empty(), "no votes");
+        let aborts = votes.iter().filter_map(|vote| match vote {
+            Commit { .. } => None,
+            Abort { reason, discord, .. } => Some((reason, discord)),
+        });
+        if let Some((reason, _)) = aborts.clone().next() {
+            let discord = match aborts.clone().any(|(_, discord)| *discord == Assertive) {
+                true => Assertive,
+                false => Permissive,
+            };
+            return Abort {
+                reason: reason.clone(),
+                discord,
+                refresh: None,
+            };
+        }
+        let safepoint = votes
+            .iter()
+            .filter_map(|vote| match vote {
+                Commit { safepoint, .. } => Some(*safepoint),
+                Abort { .. } => None,
+            })
+            .max()
+            .unwrap();
+        let discord = match votes.iter().all(|vote| *vote.discord() == Assertive) {
+            true => Assertive,
+            false => Permissive,
+        };
+        Commit { safepoint, discord }
+    }
+
     pub fn is_commit(&self) -> bool {
         match self {
             Commit {
@@ -49,6 +202,7 @@ impl Outcome {
             Abort {
                 reason: _,
                 discord: _,
+                refresh: _,
             } => false,
         }
     }
@@ -69,6 +223,7 @@ impl Outcome {
             Abort {
                 reason: _,
                 discord: _,
+                refresh: _,
             } => true,
         }
     }
@@ -79,26 +234,48 @@ impl Outcome {
                 safepoint: _,
                 discord,
             } => discord,
-            Abort { reason: _, discord } => discord,
+            Abort { reason: _, discord, refresh: _ } => discord,
         }
     }
 }
 
 #[derive(Debug, Clone)]
-pub struct Candidate {
-    pub rec: Record,
+pub struct Candidate<K = String> {
+    pub rec: Record<K>,
     pub ver: u64,
 }
 
 #[derive(Debug, Clone)]
-pub struct Record {
+pub struct Record<K = String> {
     pub xid: Uuid,
-    pub readset: Vec<String>,
-    pub writeset: Vec<String>,
+    pub readset: Vec<K>,
+    pub scans: Vec<Scan<K>>,
+    pub writeset: Vec<K>,
+    // blind writes that commute with one another, e.g., increments; a suffix truncates these among
+    // the candidate's writes, so that they are discarded along with the rest
+    pub commutes: Vec<K>,
     pub readvers: SortedVec<u64>,
     pub snapshot: u64,
 }
 
+// a record that reads and writes nothing; callers name the fields they use and take the rest from
+// here, e.g., Record { readset, writeset, ..Default::default() }
+impl<K> Default for Record<K> {
+    fn default() -> Self {
+        Self {
+            xid: Uuid::nil(),
+            readset: vec![],
+            scans: vec![],
+            writeset: vec![],
+            commutes: vec![],
+            readvers: SortedVec::default(),
+            snapshot: 0,
+        }
+    }
+}
+
+// compression does not depend on the key type; implementing it on the default instantiation
+// lets callers write Record::compress(..) without naming a key type
 impl Record {
     pub fn compress(cpt_readvers: Vec<u64>, cpt_snapshot: u64) -> (SortedVec<u64>, u64) {
         if cpt_readvers.is_empty() {
@@ -118,20 +295,633 @@ impl Record {
     }
 }
 
+// a read of every key within a range, including keys that did not exist at the time of reading
+#[derive(PartialEq, Debug, Clone)]
+pub struct Scan<K> {
+    pub start: Bound<K>,
+    pub end: Bound<K>,
+}
+
+impl<K: Key> Scan<K> {
+    pub fn new(start: Bound<K>, end: Bound<K>) -> Self {
+        Self { start, end }
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        let above_start = match &self.start {
+            Bound::Included(start) => key >= start,
+            Bound::Excluded(start) => key > start,
+            Bound::Unbounded => true,
+        };
+        let below_end = match &self.end {
+            Bound::Included(end) => key <= end,
+            Bound::Excluded(end) => key < end,
+            Bound::Unbounded => true,
+        };
+        above_start && below_end
+    }
+
+    // whether the scan cannot contain any key
+    pub fn is_empty(&self) -> bool {
+        match (&self.start, &self.end) {
+            (Bound::Included(start), Bound::Included(end)) => start > end,
+            (Bound::Included(start), Bound::Excluded(end))
+            | (Bound::Excluded(start), Bound::Included(end))
+            | (Bound::Excluded(start), Bound::Excluded(end)) => start >= end,
+            _ => false,
+        }
+    }
+
+    fn bounds(&self) -> (Bound<&K>, Bound<&K>) {
+        (self.start.as_ref(), self.end.as_ref())
+    }
+}
+
+impl Scan<String> {
+    // a scan over all keys starting with the given prefix
+    pub fn prefix(prefix: &str) -> Self {
+        let mut upper = prefix.chars().collect::<Vec<_>>();
+        let end = loop {
+            match upper.pop() {
+                None => break Bound::Unbounded,
+                Some(last) => {
+                    // the smallest string above every key with the prefix increments its last
+                    // incrementable character
+                    if let Some(next) = (last as u32 + 1..=char::MAX as u32).find_map(char::from_u32) {
+                        upper.push(next);
+                        break Bound::Excluded(upper.into_iter().collect());
+                    }
+                }
+            }
+        };
+        Self {
+            start: Bound::Included(prefix.into()),
+            end,
+        }
+    }
+}
+
+impl<K: Codec> Codec for Scan<K> {
+    fn encode(&self, writer: &mut Writer) {
+        Self::encode_bound(&self.start, writer);
+        Self::encode_bound(&self.end, writer);
+    }
+
+    fn decode(reader: &mut Reader) -> Result<Self, CheckpointError> {
+        let start = Self::decode_bound(reader)?;
+        let end = Self::decode_bound(reader)?;
+        Ok(Self { start, end })
+    }
+}
+
+impl<K: Codec> Scan<K> {
+    fn encode_bound(bound: &Bound<K>, writer: &mut Writer) {
+        match bound {
+            Bound::Included(key) => {
+                writer.u8(0);
+                key.encode(writer);
+            }
+            Bound::Excluded(key) => {
+                writer.u8(1);
+                key.encode(writer);
+            }
+            Bound::Unbounded => writer.u8(2),
+        }
+    }
+
+    fn decode_bound(reader: &mut Reader) -> Result<Bound<K>, CheckpointError> {
+        match reader.u8()? {
+            0 => Ok(Bound::Included(K::decode(reader)?)),
+            1 => Ok(Bound::Excluded(K::decode(reader)?)),
+            2 => Ok(Bound::Unbounded),
+            tag => Err(CheckpointError::InvalidTag(tag)),
+        }
+    }
+}
+
 #[derive(PartialEq, Debug, Clone)]
 pub enum AbortReason {
     Antidependency(u64),
+    Phantom { scan: usize, ver: u64 }, // the offending scan (by index) and the write within it
     Staleness,
+    WriteConflict(u64), // under snapshot isolation, a write that the candidate would overwrite
+}
+
+// cumulative counts of assessment outcomes, by the rule that decided them and, for aborts, by their
+// discord, as a commit's discord follows from its rule
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct OutcomeCounts {
+    pub r1_commits: u64,
+    pub r2_staleness_aborts: DiscordCounts,
+    pub r3_antidependency_aborts: DiscordCounts,
+    pub r3_phantom_aborts: DiscordCounts,
+    pub r3_write_conflict_aborts: DiscordCounts,
+    pub r4_commits: u64,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
+pub struct DiscordCounts {
+    pub assertive: u64,
+    pub permissive: u64,
+}
+
+impl DiscordCounts {
+    pub fn total(&self) -> u64 {
+        self.assertive + self.permissive
+    }
+}
+
+impl OutcomeCounts {
+    fn record(&mut self, outcome: &Outcome) {
+        let (reason, discord) = match outcome {
+            Commit { safepoint: _, discord: Assertive } => {
+                self.r1_commits += 1;
+                return;
+            }
+            Commit { safepoint: _, discord: Permissive } => {
+                self.r4_commits += 1;
+                return;
+            }
+            Abort { reason, discord, .. } => (reason, discord),
+        };
+        let counts = match reason {
+            Staleness => &mut self.r2_staleness_aborts,
+            Antidependency(_) => &mut self.r3_antidependency_aborts,
+            Phantom { .. } => &mut self.r3_phantom_aborts,
+            WriteConflict(_) => &mut self.r3_write_conflict_aborts,
+        };
+        match discord {
+            Assertive => counts.assertive += 1,
+            Permissive => counts.permissive += 1,
+        }
+    }
+
+    pub fn commits(&self) -> u64 {
+        self.r1_commits + self.r4_commits
+    }
+
+    pub fn aborts(&self) -> u64 {
+        self.r2_staleness_aborts.total()
+            + self.r3_antidependency_aborts.total()
+            + self.r3_phantom_aborts.total()
+            + self.r3_write_conflict_aborts.total()
+    }
+}
+
+// Mixes a digest with a run of 64-bit words by the round and the avalanche of xxHash64. Only whole
+// words are mixed, never their bytes, so that a digest is the same on every platform and in every
+// build, and can be compared across replicas and releases.
+struct DigestWords(u64);
+
+const PRIME64_1: u64 = 0x9e37_79b1_85eb_ca87;
+const PRIME64_2: u64 = 0xc2b2_ae3d_27d4_eb4f;
+const PRIME64_3: u64 = 0x1656_67b1_9e37_79f9;
+const PRIME64_4: u64 = 0x85eb_ca77_c2b2_ae63;
+
+impl DigestWords {
+    fn new(seed: u64) -> Self {
+        DigestWords(seed)
+    }
+
+    fn write(&mut self, word: u64) {
+        let round = word.wrapping_mul(PRIME64_2).rotate_left(31).wrapping_mul(PRIME64_1);
+        self.0 = (self.0 ^ round).rotate_left(27).wrapping_mul(PRIME64_1).wrapping_add(PRIME64_4);
+    }
+
+    fn finish(self) -> u64 {
+        let mut value = self.0;
+        value ^= value >> 33;
+        value = value.wrapping_mul(PRIME64_2);
+        value ^= value >> 29;
+        value = value.wrapping_mul(PRIME64_3);
+        value ^ (value >> 32)
+    }
+}
+
+// A rolling digest of the outcomes assessed so far, folded in log order. Replicas that assessed
+// the same candidates to the same outcomes have equal digests at every version, so comparing their
+// trails of digests pinpoints the first divergence, permissive outcomes included.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub struct Digest {
+    pub ver: u64, // the last version folded in
+    pub value: u64,
+}
+
+impl Digest {
+    fn fold(self, ver: u64, outcome: &Outcome) -> Self {
+        let mut words = DigestWords::new(self.value);
+        words.write(ver);
+        let discord_tag = |discord: &Discord| match discord {
+            Permissive => 0,
+            Assertive => 1,
+        };
+        match outcome {
+            Commit { safepoint, discord } => {
+                words.write(0);
+                words.write(*safepoint);
+                words.write(discord_tag(discord));
+            }
+            // refresh hints are advisory, so replicas may differ in them without diverging
+            Abort { reason, discord, refresh: _ } => {
+                match reason {
+                    Antidependency(ver) => {
+                        words.write(1);
+                        words.write(*ver);
+                    }
+                    Phantom { scan, ver } => {
+                        words.write(2);
+                        words.write(*scan as u64);
+                        words.write(*ver);
+                    }
+                    Staleness => words.write(3),
+                    WriteConflict(ver) => {
+                        words.write(4);
+                        words.write(*ver);
+                    }
+                }
+                words.write(discord_tag(discord));
+            }
+        }
+        Digest {
+            ver,
+            value: words.finish(),
+        }
+    }
+
+    // The first version at which two trails of digests, taken over the same log, differ. A rolling
+    // digest never reconverges, so the trails agree up to that version and the search can bisect.
+    pub fn first_divergence(ours: &[Digest], theirs: &[Digest]) -> Option<u64> {
+        let len = std::cmp::min(ours.len(), theirs.len());
+        let (mut low, mut high) = (0, len);
+        while low < high {
+            let mid = (low + high) / 2;
+            if ours[mid] == theirs[mid] {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        if low < len {
+            Some(std::cmp::min(ours[low].ver, theirs[low].ver))
+        } else {
+            None
+        }
+    }
+}
+
+// a point-in-time snapshot of the examiner's table sizes and cumulative counts
+#[derive(PartialEq, Debug, Clone)]
+pub struct ExaminerMetrics {
+    pub base: Option<u64>,
+    pub reads: usize,  // keys in the read table
+    pub writes: usize, // keys in the write table
+    pub scans: usize,
+    pub outcomes: OutcomeCounts,
+    pub discards: u64,
+    pub forced_advances: u64, // times the base was advanced to honour the key cap
+    pub intents: usize,       // coarse keys with reads or writes beneath them
+    pub commutes: usize,
+}
+
+// a malformed candidate or truncated entry, rejected without altering the examiner
+#[derive(PartialEq, Debug, Clone)]
+pub enum ExaminerError {
+    UnsupportedVersion,
+    Uninitialized,
+    Nonmonotonic { ver: u64, base: u64 },      // the entry preceded the base
+    SkippedVersion { existing: u64, ver: u64 }, // the entry's key was learned earlier; checked in debug builds
+    NoSuchVote { ver: u64 },                    // no vote awaits its outcome at the version
+    NotReadOnly,                                // a query's record writes
+    Interner(InternerError),                    // the entry's symbols could not be released
+    Unsupported(&'static str),                  // the record holds what the examiner cannot certify
+}
+
+impl Display for ExaminerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExaminerError::UnsupportedVersion => write!(f, "unsupported version 0"),
+            ExaminerError::Uninitialized => write!(f, "uninitialized examiner"),
+            ExaminerError::Nonmonotonic { ver, base } => {
+                write!(f, "entry.ver ({}) < self.base ({})", ver, base)
+            }
+            ExaminerError::SkippedVersion { existing, ver } => {
+                write!(f, "skipped version {} while trying to remove {}", existing, ver)
+            }
+            ExaminerError::NoSuchVote { ver } => write!(f, "no vote at {} awaits its outcome", ver),
+            ExaminerError::NotReadOnly => write!(f, "query is not read-only"),
+            ExaminerError::Interner(err) => write!(f, "{}", err),
+            ExaminerError::Unsupported(what) => write!(f, "{} are unsupported by the examiner", what),
+        }
+    }
+}
+
+impl std::error::Error for ExaminerError {}
+
+// a disagreement between the examiner's tables and the entries retained by a suffix
+#[derive(PartialEq, Debug, Clone)]
+pub enum Discrepancy<K = String> {
+    // an item learned at a version preceding the base
+    BelowBase { table: Table, key: K, ver: u64 },
+    // an item learned at a version whose entry the suffix does not retain, or retains without the key
+    Unretained { table: Table, key: K, ver: u64 },
+    // an item of an entry retained at or after the base that the examiner has not learned, nor
+    // overwritten by a later version
+    Unlearned { table: Table, key: K, ver: u64 },
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Table {
+    Reads,
+    Writes,
+    Commutes,
+}
+
+// a fuller account of an abort than AbortReason conveys
+#[derive(PartialEq, Debug, Clone)]
+pub enum AbortDetail<K = String> {
+    // every conflicting key, whether read or scanned, with the version of the write it missed
+    Antidependency { conflicts: Vec<(K, u64)> },
+    // the candidate's snapshot preceded the examiner's base
+    Staleness { snapshot: u64, base: u64 },
+    // under snapshot isolation, every written key with the version of the write it missed
+    WriteConflict { conflicts: Vec<(K, u64)> },
+}
+
+// the STRIDE rule that decided an outcome
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Rule {
+    R1, // commit write-only transactions
+    R2, // conditionally abort transactions outside the suffix
+    R3, // abort on antidependency (under snapshot isolation, on a write-write conflict)
+    R4, // conditionally commit
+}
+
+// how a candidate's key met a key previously learned by the examiner
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Intersection {
+    ReadWrite,  // the candidate writes a key read at the given version
+    WriteWrite, // the candidate writes a key written at the given version
+    WriteRead,  // the candidate reads a key written at the given version
+    ScanWrite,  // the candidate writes a key scanned at the given version
+    WriteScan,  // the candidate scans a key written at the given version
+}
+
+// the rule applied to a candidate, along with the intersections that may have raised its
+// safepoint; the safepoint is the highest intersecting version, or the version below the base
+#[derive(PartialEq, Debug, Clone)]
+pub struct Explanation<K = String> {
+    pub rule: Rule,
+    pub intersections: Vec<(Intersection, K, u64)>,
+}
+
+impl<K> Explanation<K> {
+    // translates the keys, e.g., to resolve interned keys for display
+    pub fn map<L, F: Fn(&K) -> L>(&self, f: F) -> Explanation<L> {
+        Explanation {
+            rule: self.rule,
+            intersections: self
+                .intersections
+                .iter()
+                .map(|(intersection, key, ver)| (*intersection, f(key), *ver))
+                .collect(),
+        }
+    }
+}
+
+// the outcome of applying the rules to a record, along with how it was arrived at
+struct Evaluation<K> {
+    rule: Rule,
+    outcome: Outcome,
+    detail: Option<AbortDetail<K>>,
+    key_safepoints: Vec<u64>, // of each write, followed by each commutative write, if it commits
+    intersections: Vec<(Intersection, K, u64)>, // none if it aborts
+}
+
+// what an evaluation gathers besides the outcome, each adding to its cost; all but the outcome
+// alone gather the detail of an abort, which is also gathered whenever the examiner needs it
+#[derive(PartialEq, Clone, Copy)]
+enum Gather {
+    Outcome,
+    Detail,
+    KeySafepoints,
+    Intersections,
+}
+
+impl<K> Evaluation<K> {
+    fn abort(rule: Rule, reason: AbortReason, discord: Discord, detail: AbortDetail<K>) -> Self {
+        Evaluation {
+            rule,
+            outcome: Abort { reason, discord, refresh: None },
+            detail: Some(detail),
+            key_safepoints: vec![],
+            intersections: vec![],
+        }
+    }
+
+    // drops the detail of an abort that was decided by its first conflict, and so is partial
+    fn detailed(mut self, detailed: bool) -> Self {
+        if !detailed {
+            self.detail = None;
+        }
+        self
+    }
 }
 
 impl Examiner {
-    pub fn new() -> Examiner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<K> Default for Examiner<K> {
+    fn default() -> Self {
         Examiner {
-            reads: FxHashMap::default(),
-            writes: FxHashMap::default(),
+            reads: KeyTable::new(),
+            writes: KeyTable::new(),
+            ordered_writes: None,
+            scans: VecDeque::new(),
             base: 0,
+            isolation: Isolation::default(),
+            outcomes: OutcomeCounts::default(),
+            discards: 0,
+            max_keys: None,
+            forced_base: 0,
+            forced_advances: 0,
+            parent: None,
+            read_intents: FxHashMap::default(),
+            write_intents: FxHashMap::default(),
+            commutes: KeyTable::new(),
+            refresh_hints: false,
+            digest: Digest::default(),
+            provisional: VecDeque::new(),
+            precise: false,
+            uncertain: VecDeque::new(),
+            unlearned: VecDeque::new(),
+        }
+    }
+}
+
+impl<K> Examiner<K> {
+    pub fn with_isolation(mut self, isolation: Isolation) -> Self {
+        self.isolation = isolation;
+        self
+    }
+
+    pub fn isolation(&self) -> Isolation {
+        self.isolation
+    }
+
+    // Has aborts carry a refresh hint, telling the cohort how far its replica must catch up and
+    // which keys to re-read before retrying. The hint is gathered as assess_detailed() gathers
+    // the detail of an abort, so it adds to the cost of aborts only.
+    pub fn with_refresh_hints(mut self) -> Self {
+        self.refresh_hints = true;
+        self
+    }
+
+    // Caps the number of keys (reads, writes and scans) tracked by the examiner. Once exceeded, the
+    // base is advanced past the oldest entries, which are forgotten, so that a stalled pipeline
+    // degrades to staleness aborts under R2 rather than unbounded growth.
+    pub fn with_max_keys(mut self, max_keys: usize) -> Self {
+        assert!(max_keys > 0, "invalid max_keys ({})", max_keys);
+        self.max_keys = Some(max_keys);
+        self
+    }
+
+    // Leaves the candidates that abort assertively unlearned, so that they no longer abort or hold
+    // back the candidates that follow them. Those that abort permissively are still learned, as
+    // another certifier may have committed them, but are learned as uncertain: an abort under R3 is
+    // then only assertive if it meets a write that this examiner committed, which every certifier
+    // has learned, or else discarded and so aborts as stale. Certifiers of the same log must agree
+    // on the mode. A precise certifier records how each candidate was learned in its suffix, by
+    // Suffix::set_learning(), so that an examiner rebuilt from the suffix can be made precise too.
+    pub fn with_precise_aborts(mut self) -> Self {
+        self.precise = true;
+        self
+    }
+}
+
+impl<K: Hierarchical> Examiner<K> {
+    // Treats keys as hierarchical, so that a read or write of a coarse key meets every read or
+    // write of a finer key beneath it, and vice versa. Each read or write also leaves an intent on
+    // every coarser key above it, so that a coarse key is checked against the finer keys beneath
+    // it without enumerating them.
+    pub fn with_hierarchy(mut self) -> Self {
+        self.parent = Some(K::parent);
+        // derive the intents of any entries already learned, e.g., by restore() or rebuild()
+        Self::derive_intents(&self.reads, &mut self.read_intents);
+        Self::derive_intents(&self.writes, &mut self.write_intents);
+        Self::derive_intents(&self.commutes, &mut self.write_intents);
+        self
+    }
+
+    fn derive_intents(items: &KeyTable<K>, intents: &mut FxHashMap<K, u64>) {
+        for (key, &ver) in items.iter() {
+            let mut ancestor = K::parent(key);
+            while let Some(key) = ancestor {
+                ancestor = K::parent(&key);
+                let intent = intents.entry(key).or_insert(ver);
+                if ver > *intent {
+                    *intent = ver;
+                }
+            }
         }
     }
+}
+
+impl<K: Key> Examiner<K> {
+    // Rebuilds the examiner from the entries retained by a suffix, e.g., when recovering a
+    // certifier, which then need only replay the log from the suffix's low-water mark. Entries
+    // appended as records carry their commutative writes and scans, which are recovered as such;
+    // the base follows the last entry the suffix truncated, as it would for an examiner that
+    // discarded it. Entries are learned as the suffix records, so that those left unlearned or
+    // learned as uncertain in precise mode remain so. Outcomes are not retained, so the rebuilt
+    // examiner's digest starts afresh.
+    pub fn rebuild(suffix: &Suffix<K>) -> Self {
+        let mut examiner = Self::default();
+        for ver in suffix.range() {
+            if let Some(entry) = suffix.get(ver) {
+                match entry.learning {
+                    Learning::Certain => {}
+                    Learning::Uncertain => examiner.uncertain.push_back(ver),
+                    Learning::Unlearned => {
+                        examiner.unlearned.push_back(ver);
+                        continue;
+                    }
+                }
+                for read in &entry.readset {
+                    examiner.reads.insert(read, ver);
+                }
+                for scan in &entry.scans {
+                    examiner.scans.push_back((ver, scan.clone()));
+                }
+                for write in &entry.writeset {
+                    examiner.writes.insert(write, ver);
+                }
+                for commute in &entry.commutes {
+                    examiner.commutes.insert(commute, ver);
+                }
+            }
+        }
+        examiner.base = match suffix.last_truncated() {
+            Some(last_truncated) => last_truncated + 1,
+            None => suffix.lwm().unwrap_or(0),
+        };
+        examiner
+    }
+
+    // Cross-checks the read, write and commute tables against the entries retained by the suffix
+    // that the examiner's candidates were appended to, returning every discrepancy. The examiner
+    // must have assessed every entry appended so far and discarded every entry truncated; entries
+    // that it forgot by forcing its base forward are not expected to be learned. Commutative writes
+    // are expected among the commutes of entries appended as records, or else among their writes,
+    // while the entries left unlearned in precise mode are not expected at all.
+    pub fn validate(&self, suffix: &Suffix<K>) -> Vec<Discrepancy<K>> {
+        let mut discrepancies = vec![];
+        let tables = [
+            (Table::Reads, &self.reads),
+            (Table::Writes, &self.writes),
+            (Table::Commutes, &self.commutes),
+        ];
+        for &(table, items) in &tables {
+            for (key, &ver) in items.iter() {
+                let retained = match (suffix.get(ver), table) {
+                    (None, _) => false,
+                    (Some(entry), Table::Reads) => entry.readset.contains(key),
+                    (Some(entry), Table::Writes) => entry.writeset.contains(key),
+                    (Some(entry), Table::Commutes) => entry.commutes.contains(key) || entry.writeset.contains(key),
+                };
+                if ver < self.base {
+                    discrepancies.push(Discrepancy::BelowBase { table, key: key.clone(), ver });
+                } else if !retained {
+                    discrepancies.push(Discrepancy::Unretained { table, key: key.clone(), ver });
+                }
+            }
+        }
+
+        for ver in std::cmp::max(suffix.range().start, self.base)..suffix.range().end {
+            if self.unlearned.binary_search(&ver).is_ok() {
+                continue;
+            }
+            if let Some(entry) = suffix.get(ver) {
+                for read in &entry.readset {
+                    if self.reads.get(read).copied().unwrap_or(0) < ver {
+                        let key = read.clone();
+                        discrepancies.push(Discrepancy::Unlearned { table: Table::Reads, key, ver });
+                    }
+                }
+                for write in entry.writeset.iter().chain(&entry.commutes) {
+                    let self_write = self.writes.get(write).copied().unwrap_or(0);
+                    let self_commute = self.commutes.get(write).copied().unwrap_or(0);
+                    if std::cmp::max(self_write, self_commute) < ver {
+                        let key = write.clone();
+                        discrepancies.push(Discrepancy::Unlearned { table: Table::Writes, key, ver });
+                    }
+                }
+            }
+        }
+        discrepancies
+    }
 
     fn ensure_initialized(&mut self, ver: u64) {
         if self.base == 0 {
@@ -139,124 +929,804 @@ impl Examiner {
         }
     }
 
-    pub fn learn(&mut self, candidate: Candidate) {
-        assert_ne!(0, candidate.ver, "unsupported version 0");
-        self.ensure_initialized(candidate.ver);
-        for read in candidate.rec.readset {
-            self.reads.insert(read, candidate.ver);
+    pub fn learn(&mut self, candidate: Candidate<K>) {
+        self.try_learn(candidate).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_learn(&mut self, candidate: Candidate<K>) -> Result<(), ExaminerError> {
+        self.try_learn_record(&candidate.rec, candidate.ver)
+    }
+
+    // learns a candidate's record without taking ownership, cloning only the keys that are new
+    // to the examiner
+    pub fn learn_record(&mut self, rec: &Record<K>, ver: u64) {
+        self.try_learn_record(rec, ver).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_learn_record(&mut self, rec: &Record<K>, ver: u64) -> Result<(), ExaminerError> {
+        if ver == 0 {
+            return Err(ExaminerError::UnsupportedVersion);
+        }
+        self.ensure_initialized(ver);
+        self.learn_items(rec, ver);
+        self.enforce_max_keys();
+        Ok(())
+    }
+
+    fn learn_items(&mut self, rec: &Record<K>, ver: u64) {
+        for read in &rec.readset {
+            self.learn_read(read, ver);
+        }
+
+        for scan in &rec.scans {
+            self.scans.push_back((ver, scan.clone()));
         }
 
-        for write in candidate.rec.writeset {
-            self.writes.insert(write, candidate.ver);
+        for write in &rec.writeset {
+            self.learn_write(write, ver);
         }
+
+        for commute in &rec.commutes {
+            Self::mark_intents(&mut self.write_intents, self.parent, commute, ver);
+            self.commutes.insert(commute, ver);
+        }
+    }
+
+    fn learn_read(&mut self, read: &K, ver: u64) {
+        Self::mark_intents(&mut self.read_intents, self.parent, read, ver);
+        self.reads.insert(read, ver);
     }
 
-    fn update_writes_and_compute_safepoint(&mut self, writeset: Vec<String>, ver: u64) -> u64 {
-        let mut safepoint = 0;
-        for candidate_write in writeset {
-            // update safepoint for read-write intersection
-            if let Some(&self_read) = self.reads.get(&candidate_write) {
-                if self_read > safepoint {
-                    safepoint = self_read;
+    // learns the write, returning the version of the write it replaced
+    fn learn_write(&mut self, write: &K, ver: u64) -> Option<u64> {
+        if let Some(ordered_writes) = &mut self.ordered_writes {
+            match ordered_writes.get_mut(write) {
+                Some(self_write) => *self_write = ver,
+                None => {
+                    ordered_writes.insert(write.clone(), ver);
                 }
             }
+        }
+        Self::mark_intents(&mut self.write_intents, self.parent, write, ver);
+        self.writes.insert(write, ver)
+    }
 
-            // update safepoint for write-write intersection and learn the write
-            match self.writes.entry(candidate_write) {
-                Entry::Occupied(mut entry) => {
-                    let self_write = entry.insert(ver);
-                    if self_write > safepoint {
-                        safepoint = self_write
-                    }
+    // the coarser keys above the given one, nearest first; none unless keys are hierarchical
+    fn ancestors(&self, key: &K) -> Vec<K> {
+        let mut ancestors = vec![];
+        if let Some(parent) = self.parent {
+            let mut ancestor = parent(key);
+            while let Some(key) = ancestor {
+                ancestor = parent(&key);
+                ancestors.push(key);
+            }
+        }
+        ancestors
+    }
+
+    fn mark_intents(
+        intents: &mut FxHashMap<K, u64>,
+        parent: Option<fn(&K) -> Option<K>>,
+        key: &K,
+        ver: u64,
+    ) {
+        if let Some(parent) = parent {
+            let mut ancestor = parent(key);
+            while let Some(key) = ancestor {
+                ancestor = parent(&key);
+                intents.insert(key, ver);
+            }
+        }
+    }
+
+    // the versions of the reads of keys coarser or finer than the given one
+    fn hierarchical_reads(&self, key: &K) -> Vec<u64> {
+        Self::hierarchical_items(&[&self.reads], &self.read_intents, &self.ancestors(key), key)
+    }
+
+    // the versions of the writes of keys coarser or finer than the given one
+    fn hierarchical_writes(&self, key: &K) -> Vec<u64> {
+        let tables = [&self.writes, &self.commutes];
+        Self::hierarchical_items(&tables, &self.write_intents, &self.ancestors(key), key)
+    }
+
+    fn hierarchical_items(
+        tables: &[&KeyTable<K>],
+        intents: &FxHashMap<K, u64>,
+        ancestors: &[K],
+        key: &K,
+    ) -> Vec<u64> {
+        if ancestors.is_empty() && intents.is_empty() {
+            return vec![];
+        }
+        ancestors
+            .iter()
+            .flat_map(|ancestor| tables.iter().filter_map(move |items| items.get(ancestor)))
+            .chain(intents.get(key))
+            .copied()
+            .collect()
+    }
+
+    fn tracked_keys(&self) -> usize {
+        self.reads.len()
+            + self.writes.len()
+            + self.scans.len()
+            + self.read_intents.len()
+            + self.write_intents.len()
+            + self.commutes.len()
+    }
+
+    fn enforce_max_keys(&mut self) {
+        let max_keys = match self.max_keys {
+            Some(max_keys) if self.tracked_keys() > max_keys => max_keys,
+            _ => return,
+        };
+        // evict down to three quarters of the cap, so that the cost of eviction is amortized
+        let excess = self.tracked_keys() - (max_keys - max_keys / 4);
+        let mut vers = self
+            .reads
+            .values()
+            .chain(self.writes.values())
+            .chain(self.read_intents.values())
+            .chain(self.write_intents.values())
+            .chain(self.commutes.values())
+            .copied()
+            .chain(self.scans.iter().map(|(scan_ver, _)| *scan_ver))
+            .collect::<Vec<_>>();
+        let (_, &mut newest_evicted, _) = vers.select_nth_unstable(excess - 1);
+        self.force_base(newest_evicted + 1);
+    }
+
+    fn force_base(&mut self, base: u64) {
+        self.reads.discard_through(base - 1);
+        self.writes.discard_through(base - 1);
+        self.read_intents.retain(|_, ver| *ver >= base);
+        self.write_intents.retain(|_, ver| *ver >= base);
+        self.commutes.discard_through(base - 1);
+        if let Some(ordered_writes) = &mut self.ordered_writes {
+            ordered_writes.retain(|_, ver| *ver >= base);
+        }
+        while let Some((scan_ver, _)) = self.scans.front() {
+            if *scan_ver >= base {
+                break;
+            }
+            self.scans.pop_front();
+        }
+        Self::retire_through(&mut self.uncertain, base - 1);
+        Self::retire_through(&mut self.unlearned, base - 1);
+        if base > self.base {
+            self.base = base;
+        }
+        self.forced_base = self.base;
+        self.forced_advances += 1;
+    }
+
+    // the ordered write index is only needed once scans are in play, so it is built on demand
+    fn ensure_ordered_writes(&mut self) {
+        if self.ordered_writes.is_none() {
+            let writes = &self.writes;
+            self.ordered_writes = Some(
+                writes
+                    .iter()
+                    .map(|(write, &ver)| (write.clone(), ver))
+                    .collect(),
+            );
+        }
+    }
+
+    // the version of the last scan to contain the key
+    fn scanned_by(&self, key: &K) -> Option<u64> {
+        self.scans
+            .iter()
+            .rev()
+            .find(|(_, self_scan)| self_scan.contains(key))
+            .map(|(self_scan_ver, _)| *self_scan_ver)
+    }
+
+    // the commutative writes within the scan, in key order; commutative writes are not ordered, so
+    // the scan is a sweep
+    fn scanned_commutes(&self, scan: &Scan<K>) -> Vec<(&K, u64)> {
+        if scan.is_empty() {
+            return vec![];
+        }
+        let mut scanned = self
+            .commutes
+            .iter()
+            .filter(|(self_commute_key, _)| scan.contains(self_commute_key))
+            .map(|(self_commute_key, &self_commute)| (self_commute_key, self_commute))
+            .collect::<Vec<_>>();
+        scanned.sort_unstable_by_key(|&(self_commute_key, _)| self_commute_key);
+        scanned
+    }
+
+    pub fn assess(&mut self, candidate: Candidate<K>) -> Outcome {
+        self.try_assess(candidate).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_assess(&mut self, candidate: Candidate<K>) -> Result<Outcome, ExaminerError> {
+        self.try_assess_record(&candidate.rec, candidate.ver)
+    }
+
+    // assesses a candidate's record without taking ownership, cloning only the keys that are new
+    // to the examiner
+    pub fn assess_record(&mut self, rec: &Record<K>, ver: u64) -> Outcome {
+        self.try_assess_record(rec, ver).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_assess_record(&mut self, rec: &Record<K>, ver: u64) -> Result<Outcome, ExaminerError> {
+        self.try_assess_evaluated(rec, ver, Gather::Outcome).map(|evaluation| evaluation.outcome)
+    }
+
+    fn try_assess_evaluated(&mut self, rec: &Record<K>, ver: u64, gather: Gather) -> Result<Evaluation<K>, ExaminerError> {
+        if ver == 0 {
+            return Err(ExaminerError::UnsupportedVersion);
+        }
+        Ok(self.assess_evaluated(rec, ver, gather))
+    }
+
+    fn assess_evaluated(&mut self, rec: &Record<K>, ver: u64, gather: Gather) -> Evaluation<K> {
+        let evaluation = self.assess_valid(rec, ver, gather);
+        self.outcomes.record(&evaluation.outcome);
+        self.digest = self.digest.fold(ver, &evaluation.outcome);
+        self.enforce_max_keys();
+        evaluation
+    }
+
+    // Assesses a partition's slice of a record spanning several partitions, each certified by its
+    // own examiner, yielding the partition's vote. The votes are combined by Outcome::combine()
+    // and whether the combined outcome commits is handed back to resolve(). Meanwhile the slice is learned
+    // provisionally, so that the candidates that follow it are assessed as if it committed.
+    pub fn vote(&mut self, rec: &Record<K>, ver: u64) -> Outcome {
+        self.try_vote(rec, ver).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_vote(&mut self, rec: &Record<K>, ver: u64) -> Result<Outcome, ExaminerError> {
+        let priors = |items: &KeyTable<K>, keys: &[K]| {
+            keys.iter()
+                .map(|key| (key.clone(), items.get(key).copied()))
+                .collect::<Vec<_>>()
+        };
+        let intent_priors = |intents: &FxHashMap<K, u64>, keys: &[&[K]]| {
+            keys.iter()
+                .flat_map(|keys| keys.iter())
+                .flat_map(|key| self.ancestors(key))
+                .map(|ancestor| {
+                    let prior = intents.get(&ancestor).copied();
+                    (ancestor, prior)
+                })
+                .collect::<Vec<_>>()
+        };
+        let provisional = Provisional {
+            ver,
+            reads: priors(&self.reads, &rec.readset),
+            writes: priors(&self.writes, &rec.writeset),
+            commutes: priors(&self.commutes, &rec.commutes),
+            read_intents: intent_priors(&self.read_intents, &[&rec.readset]),
+            write_intents: intent_priors(&self.write_intents, &[&rec.writeset, &rec.commutes]),
+        };
+        let outcome = self.try_assess_record(rec, ver)?;
+        self.provisional.push_back(provisional);
+        Ok(outcome)
+    }
+
+    // Settles the vote at the given version once the combined outcome is known. A committed slice
+    // stays learned, while an aborted one is rolled back: each of its keys, and each intent that it
+    // left on a coarser key, reverts to the version it replaced, unless a later candidate has since
+    // replaced it, or the replaced version has fallen below the base. The slice's scans are forgotten.
+    pub fn resolve(&mut self, ver: u64, committed: bool) {
+        self.try_resolve(ver, committed).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_resolve(&mut self, ver: u64, committed: bool) -> Result<(), ExaminerError> {
+        let index = self
+            .provisional
+            .iter()
+            .position(|provisional| provisional.ver == ver)
+            .ok_or(ExaminerError::NoSuchVote { ver })?;
+        let provisional = self.provisional.remove(index).unwrap();
+        if committed {
+            return Ok(());
+        }
+
+        // the later votes that replaced the slice's keys now replace the versions it reverts to
+        for later in self.provisional.iter_mut().skip(index) {
+            Self::repoint_priors(&mut later.reads, &provisional.reads, ver);
+            Self::repoint_priors(&mut later.writes, &provisional.writes, ver);
+            Self::repoint_priors(&mut later.commutes, &provisional.commutes, ver);
+            Self::repoint_priors(&mut later.read_intents, &provisional.read_intents, ver);
+            Self::repoint_priors(&mut later.write_intents, &provisional.write_intents, ver);
+        }
+        if let Some(ordered_writes) = &mut self.ordered_writes {
+            for (write, prior) in provisional.writes.iter().rev() {
+                if ordered_writes.get(write) == Some(&ver) {
+                    match prior {
+                        Some(prior) if *prior >= self.base => ordered_writes.insert(write.clone(), *prior),
+                        _ => ordered_writes.remove(write),
+                    };
                 }
-                Entry::Vacant(entry) => {
-                    entry.insert(ver);
+            }
+        }
+        Self::revert_items(&mut self.reads, &provisional.reads, ver, self.base);
+        Self::revert_items(&mut self.writes, &provisional.writes, ver, self.base);
+        Self::revert_items(&mut self.commutes, &provisional.commutes, ver, self.base);
+        Self::revert_intents(&mut self.read_intents, &provisional.read_intents, ver, self.base);
+        Self::revert_intents(&mut self.write_intents, &provisional.write_intents, ver, self.base);
+        self.scans.retain(|(scan_ver, _)| *scan_ver != ver);
+        Ok(())
+    }
+
+    fn revert_items(items: &mut KeyTable<K>, priors: &[(K, Option<u64>)], ver: u64, base: u64) {
+        // in reverse, so that a key repeated within the slice reverts to the version before it
+        for (key, prior) in priors.iter().rev() {
+            if items.get(key) == Some(&ver) {
+                match prior {
+                    Some(prior) if *prior >= base => items.insert(key, *prior),
+                    _ => items.remove(key),
+                };
+            }
+        }
+    }
+
+    fn revert_intents(intents: &mut FxHashMap<K, u64>, priors: &[(K, Option<u64>)], ver: u64, base: u64) {
+        for (key, prior) in priors.iter().rev() {
+            if intents.get(key) == Some(&ver) {
+                match prior {
+                    Some(prior) if *prior >= base => intents.insert(key.clone(), *prior),
+                    _ => intents.remove(key),
+                };
+            }
+        }
+    }
+
+    fn repoint_priors(later: &mut [(K, Option<u64>)], priors: &[(K, Option<u64>)], ver: u64) {
+        for (key, later_prior) in later {
+            if *later_prior == Some(ver) {
+                if let Some((_, prior)) = priors.iter().find(|(prior_key, _)| prior_key == key) {
+                    *later_prior = *prior;
                 }
             }
         }
-        safepoint
     }
 
-    pub fn assess(&mut self, candidate: Candidate) -> Outcome {
-        assert_ne!(0, candidate.ver, "unsupported version 0");
-        self.ensure_initialized(candidate.ver);
-        let mut safepoint = self.base - 1;
-
-        // rule R1: commit write-only transactions
-        if candidate.rec.readset.is_empty() {
-            // update safepoint for read-write and write-write intersection, and learn the writes
-            let tmp_safepoint =
-                self.update_writes_and_compute_safepoint(candidate.rec.writeset, candidate.ver);
-            if tmp_safepoint > safepoint {
-                safepoint = tmp_safepoint;
-            }
-            return Commit {
-                safepoint,
-                discord: Assertive,
-            };
+    // the refresh hint for an abort with the given detail, if the examiner gives hints
+    fn refresh(&self, rec: &Record<K>, detail: Option<&AbortDetail<K>>) -> Option<Refresh> {
+        match self.refresh_hints {
+            true => detail.map(|detail| Refresh::new(rec, detail)),
+            false => None,
         }
+    }
+
+    // in precise mode, an abort under R3 stays assertive only if it meets a write learned with
+    // certainty
+    fn qualify(&self, discord: Discord, detail: Option<&AbortDetail<K>>) -> Discord {
+        if !self.precise || discord == Permissive {
+            return discord;
+        }
+        let conflicts = match detail {
+            Some(AbortDetail::Antidependency { conflicts }) | Some(AbortDetail::WriteConflict { conflicts }) => {
+                conflicts.as_slice()
+            }
+            _ => &[],
+        };
+        match conflicts.iter().any(|(_, conflict)| self.uncertain.binary_search(conflict).is_err()) {
+            true => Assertive,
+            false => Permissive,
+        }
+    }
+
+    fn insert_ordered(vers: &mut VecDeque<u64>, ver: u64) {
+        let index = vers.partition_point(|&other| other < ver);
+        vers.insert(index, ver);
+    }
+
+    // applies the rules to the record, then learns it; an aborted record is learned all the same, as
+    // learning is independent of the outcome, unless in precise mode
+    fn assess_valid(&mut self, rec: &Record<K>, ver: u64, gather: Gather) -> Evaluation<K> {
+        self.ensure_initialized(ver);
+        if !rec.scans.is_empty() {
+            self.ensure_ordered_writes();
+        }
+        let mut evaluation = self.evaluate(rec, ver, gather);
+        match &mut evaluation.outcome {
+            Commit { .. } => self.learn_items(rec, ver),
+            Abort { reason: _, discord, refresh } => {
+                *discord = self.qualify(discord.clone(), evaluation.detail.as_ref());
+                *refresh = self.refresh(rec, evaluation.detail.as_ref());
+                if !self.precise {
+                    self.learn_items(rec, ver);
+                } else if *discord == Permissive {
+                    self.learn_items(rec, ver);
+                    Self::insert_ordered(&mut self.uncertain, ver);
+                } else {
+                    Self::insert_ordered(&mut self.unlearned, ver);
+                }
+            }
+        }
+        evaluation
+    }
+
+    // Applies the rules to the record, leaving the examiner unchanged. The reads and scans are met
+    // first, raising the safepoint shared by every write; a read or scan that missed the write it
+    // meets is an antidependency. Each write is met next, raising its own safepoint; under snapshot
+    // isolation, a write that missed the write it meets is a conflict. Unless the detail is gathered,
+    // the first conflict decides the abort, and the tables of unused features are not consulted.
+    fn evaluate(&self, rec: &Record<K>, ver: u64, gather: Gather) -> Evaluation<K> {
+        let base = if self.base == 0 { ver } else { self.base };
+        let write_only = rec.readset.is_empty() && rec.scans.is_empty();
 
         // rule R2: conditionally abort transactions outside the suffix
-        if candidate.rec.snapshot < self.base - 1 {
-            // println!("{} VENTURING snapshot {} base {}", candidate.ver, candidate.rec.snapshot, self.base);
-            self.learn(candidate);
-            return Abort {
-                reason: Staleness,
-                discord: Permissive,
+        if !write_only && rec.snapshot < base - 1 {
+            let detail = AbortDetail::Staleness {
+                snapshot: rec.snapshot,
+                base,
             };
+            return Evaluation::abort(Rule::R2, Staleness, Permissive, detail);
         }
 
-        // rule R3: abort on antidependency
-        for candidate_read in candidate.rec.readset.iter() {
+        // a write is missed if not within the snapshot, unless read; a commutative write is not
+        // identified by the version read, so it is missed if not within the snapshot
+        let missed = |self_write: u64| self_write > rec.snapshot && !rec.readvers.contains(&self_write);
+        let missed_commute = |self_commute: u64| self_commute > rec.snapshot;
+        let serializable = self.isolation == Serializable;
+        let snapshot = self.isolation == Snapshot && !write_only;
+        let detailed = gather != Gather::Outcome || self.precise || self.refresh_hints;
+        let (commutes, scans, hierarchical) = (!self.commutes.is_empty(), !self.scans.is_empty(), self.parent.is_some());
+
+        // meet the reads, including through a coarser or finer key, and the scans
+        let mut intersections = vec![];
+        let mut shared = base - 1;
+        let mut antidependencies = vec![];
+        let mut conflicted = false;
+        let mut meet = |intersection, key: &K, self_ver, missed: bool, scan| {
+            if gather == Gather::Intersections {
+                intersections.push((intersection, key.clone(), self_ver));
+            }
+            shared = std::cmp::max(shared, self_ver);
+            let conflict = serializable && missed;
+            if conflict {
+                antidependencies.push((key.clone(), self_ver, scan));
+            }
+            conflict
+        };
+        for candidate_read in &rec.readset {
             if let Some(&self_write) = self.writes.get(candidate_read) {
-                if self_write > candidate.rec.snapshot
-                    && !candidate.rec.readvers.contains(&self_write)
-                {
-                    self.learn(candidate);
-                    return Abort {
-                        reason: Antidependency(self_write),
-                        discord: Assertive,
-                    };
+                conflicted |= meet(Intersection::WriteRead, candidate_read, self_write, missed(self_write), None);
+            }
+            if commutes {
+                if let Some(&self_commute) = self.commutes.get(candidate_read) {
+                    conflicted |= meet(Intersection::WriteRead, candidate_read, self_commute, missed_commute(self_commute), None);
                 }
+            }
+            if hierarchical {
+                for self_write in self.hierarchical_writes(candidate_read) {
+                    conflicted |= meet(Intersection::WriteRead, candidate_read, self_write, missed(self_write), None);
+                }
+            }
+            if !detailed && conflicted {
+                break;
+            }
+        }
+        for (scan_index, scan) in rec.scans.iter().enumerate() {
+            if !detailed && conflicted {
+                break;
+            }
+            for (self_write_key, self_write) in self.scanned_writes(scan) {
+                conflicted |= meet(Intersection::WriteScan, self_write_key, self_write, missed(self_write), Some(scan_index));
+            }
+            for (self_commute_key, self_commute) in self.scanned_commutes(scan) {
+                let missed = missed_commute(self_commute);
+                conflicted |= meet(Intersection::WriteScan, self_commute_key, self_commute, missed, Some(scan_index));
+            }
+        }
+
+        // rule R3: abort on antidependency, or on a phantom write within a scanned range
+        if let Some(&(_, self_ver, scan)) = antidependencies.first() {
+            let reason = match scan {
+                Some(scan) => Phantom { scan, ver: self_ver },
+                None => Antidependency(self_ver),
+            };
+            let conflicts = antidependencies
+                .into_iter()
+                .map(|(key, self_ver, _)| (key, self_ver))
+                .collect();
+            let detail = AbortDetail::Antidependency { conflicts };
+            return Evaluation::abort(Rule::R3, reason, Assertive, detail).detailed(detailed);
+        }
 
-                // update safepoint for write-read intersection
-                if self_write > safepoint {
-                    safepoint = self_write;
+        // meet the writes, followed by the commutative writes, which commute only with one another
+        let mut safepoint = shared;
+        let mut key_safepoints = vec![];
+        let mut write_conflicts = vec![];
+        for (write_index, candidate_write) in rec.writeset.iter().chain(&rec.commutes).enumerate() {
+            let overwrite = write_index < rec.writeset.len();
+            let mut key_safepoint = shared;
+            // a key repeated in the writeset meets its own earlier write
+            if overwrite && rec.writeset[..write_index].contains(candidate_write) {
+                key_safepoint = ver;
+            }
+            let mut meet = |intersection, self_ver, conflict: bool| {
+                if gather == Gather::Intersections {
+                    intersections.push((intersection, candidate_write.clone(), self_ver));
+                }
+                key_safepoint = std::cmp::max(key_safepoint, self_ver);
+                if snapshot && conflict {
+                    write_conflicts.push((candidate_write.clone(), self_ver));
+                }
+            };
+            if let Some(&self_read) = self.reads.get(candidate_write) {
+                meet(Intersection::ReadWrite, self_read, false);
+            }
+            if scans {
+                if let Some(self_scan_ver) = self.scanned_by(candidate_write) {
+                    meet(Intersection::ScanWrite, self_scan_ver, false);
                 }
             }
+            if let Some(&self_write) = self.writes.get(candidate_write) {
+                meet(Intersection::WriteWrite, self_write, overwrite && missed(self_write));
+            }
+            if overwrite && commutes {
+                if let Some(&self_commute) = self.commutes.get(candidate_write) {
+                    meet(Intersection::WriteWrite, self_commute, missed_commute(self_commute));
+                }
+            }
+            if hierarchical {
+                for self_read in self.hierarchical_reads(candidate_write) {
+                    meet(Intersection::ReadWrite, self_read, false);
+                }
+                for self_write in self.hierarchical_writes(candidate_write) {
+                    meet(Intersection::WriteWrite, self_write, overwrite && missed(self_write));
+                }
+            }
+            if gather == Gather::KeySafepoints {
+                key_safepoints.push(key_safepoint);
+            }
+            safepoint = std::cmp::max(safepoint, key_safepoint);
+            if !detailed && !write_conflicts.is_empty() {
+                break;
+            }
         }
 
-        // rule R4 conditionally commit
+        // rule R3 under snapshot isolation: abort on a write-write conflict; stale reads and
+        // phantoms are admitted, but still raise the safepoint
+        if let Some(&(_, self_ver)) = write_conflicts.first() {
+            let detail = AbortDetail::WriteConflict { conflicts: write_conflicts };
+            return Evaluation::abort(Rule::R3, WriteConflict(self_ver), Assertive, detail).detailed(detailed);
+        }
 
-        // update safepoint for read-write and write-write intersection, and learn the writes
-        let tmp_safepoint =
-            self.update_writes_and_compute_safepoint(candidate.rec.writeset, candidate.ver);
-        if tmp_safepoint > safepoint {
-            safepoint = tmp_safepoint;
+        // rules R1 and R4: commit write-only transactions, and conditionally commit the rest
+        Evaluation {
+            rule: if write_only { Rule::R1 } else { Rule::R4 },
+            outcome: Commit {
+                safepoint,
+                discord: if write_only { Assertive } else { Permissive },
+            },
+            detail: None,
+            key_safepoints,
+            intersections,
         }
+    }
+
+    // Assesses a run of candidates in log order, yielding the same outcomes as assessing them one at
+    // a time, without taking ownership. Every version is checked before any candidate is assessed,
+    // and the tables reserve room up front for each key the run could add, so that none of them
+    // grows, rehashing its keys, partway through the run.
+    pub fn assess_batch(&mut self, candidates: &[Candidate<K>]) -> Vec<Outcome> {
+        self.try_assess_batch(candidates).unwrap_or_else(|err| panic!("{}", err))
+    }
 
-        // learn the reads
-        for candidate_read in candidate.rec.readset {
-            self.reads.insert(candidate_read, candidate.ver);
+    pub fn try_assess_batch(&mut self, candidates: &[Candidate<K>]) -> Result<Vec<Outcome>, ExaminerError> {
+        if candidates.iter().any(|candidate| candidate.ver == 0) {
+            return Err(ExaminerError::UnsupportedVersion);
+        }
+        let (mut num_reads, mut num_writes, mut num_commutes) = (0, 0, 0);
+        for candidate in candidates {
+            num_reads += candidate.rec.readset.len();
+            num_writes += candidate.rec.writeset.len();
+            num_commutes += candidate.rec.commutes.len();
         }
+        self.reads.reserve(num_reads);
+        self.writes.reserve(num_writes);
+        self.commutes.reserve(num_commutes);
 
-        Commit {
-            safepoint,
-            discord: Permissive,
+        let mut outcomes = Vec::with_capacity(candidates.len());
+        for candidate in candidates {
+            let evaluation = self.assess_evaluated(&candidate.rec, candidate.ver, Gather::Outcome);
+            outcomes.push(evaluation.outcome);
         }
+        Ok(outcomes)
     }
 
-    pub fn discard(&mut self, entry: TruncatedEntry) {
-        assert_ne!(self.base, 0, "uninitialized examiner");
-        assert!(
-            entry.ver >= self.base,
-            "entry.ver ({}) < self.base ({})",
-            entry.ver,
-            self.base
-        );
-        Self::remove_items(&mut self.reads, entry.readset, entry.ver);
-        Self::remove_items(&mut self.writes, entry.writeset, entry.ver);
+    // the outcome that assess() would produce for the candidate, leaving the examiner unchanged
+    pub fn peek(&self, candidate: &Candidate<K>) -> Outcome {
+        assert_ne!(0, candidate.ver, "unsupported version 0");
+        self.peek_record(&candidate.rec, candidate.ver)
+    }
+
+    fn peek_record(&self, rec: &Record<K>, ver: u64) -> Outcome {
+        let evaluation = self.evaluate(rec, ver, Gather::Outcome);
+        match evaluation.outcome {
+            Abort { reason, discord, refresh: _ } => Abort {
+                reason,
+                discord: self.qualify(discord, evaluation.detail.as_ref()),
+                refresh: self.refresh(rec, evaluation.detail.as_ref()),
+            },
+            outcome => outcome,
+        }
+    }
+
+    pub fn would_commit(&self, candidate: &Candidate<K>) -> bool {
+        self.peek(candidate).is_commit()
+    }
+
+    // Certifies a read-only record against the writes learned so far, without giving it a version
+    // or learning it, so that a read-only transaction need not be appended to the log. The outcome
+    // is relative to the examiner's current position: a commit means that the record read the last
+    // write of each of its keys learned so far, or one that the examiner has since discarded, with
+    // the safepoint being the version that its replica must reach to have installed those writes.
+    // The rules apply as they would to a candidate, so a snapshot behind the base is stale under
+    // R2. As queries are not learned, they are neither counted among the outcomes nor folded into
+    // the digest, and replicated examiners at different positions may answer them differently.
+    pub fn query(&self, rec: &Record<K>) -> Outcome {
+        self.try_query(rec).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_query(&self, rec: &Record<K>) -> Result<Outcome, ExaminerError> {
+        if !rec.writeset.is_empty() || !rec.commutes.is_empty() {
+            return Err(ExaminerError::NotReadOnly);
+        }
+        // the version only stands in for the base of an uninitialized examiner
+        Ok(self.peek_record(rec, std::cmp::max(self.base, 1)))
+    }
+
+    // Assesses the candidate's record as assess_record() would, also yielding the safepoint of each
+    // of its writes, followed by each of its commutative writes, if it commits. A write's safepoint
+    // covers the intersections with the candidate's reads and scans, as any write may derive from
+    // them, but only the intersections of that write's own key, so a replica may install the write
+    // once it reaches that safepoint, even if short of the candidate's. The commit's safepoint is the
+    // highest of its writes'.
+    pub fn assess_keyed(&mut self, rec: &Record<K>, ver: u64) -> (Outcome, Vec<u64>) {
+        let evaluation = self.try_assess_evaluated(rec, ver, Gather::KeySafepoints).unwrap_or_else(|err| panic!("{}", err));
+        (evaluation.outcome, evaluation.key_safepoints)
+    }
+
+    // the learned writes within the scan, in key order
+    fn scanned_writes(&self, scan: &Scan<K>) -> Vec<(&K, u64)> {
+        if scan.is_empty() {
+            return vec![];
+        }
+        match &self.ordered_writes {
+            Some(ordered_writes) => ordered_writes
+                .range(scan.bounds())
+                .map(|(self_write_key, &self_write)| (self_write_key, self_write))
+                .collect(),
+            None => {
+                // the ordered index is yet to be built; fall back to a sorted sweep of all writes
+                let mut scanned = self
+                    .writes
+                    .iter()
+                    .filter(|(self_write_key, _)| scan.contains(self_write_key))
+                    .collect::<Vec<_>>();
+                scanned.sort_unstable_by_key(|&(self_write_key, _)| self_write_key);
+                scanned.into_iter().map(|(self_write_key, &self_write)| (self_write_key, self_write)).collect()
+            }
+        }
+    }
+
+    // assesses the candidate as assess() would, also detailing the cause of an abort; the detail is
+    // present iff the assessment aborts
+    pub fn assess_detailed(&mut self, candidate: Candidate<K>) -> (Outcome, Option<AbortDetail<K>>) {
+        let evaluation = self
+            .try_assess_evaluated(&candidate.rec, candidate.ver, Gather::Detail)
+            .unwrap_or_else(|err| panic!("{}", err));
+        (evaluation.outcome, evaluation.detail)
+    }
+
+    // assesses the candidate as assess() would, also explaining how the outcome was arrived at
+    pub fn assess_explained(&mut self, candidate: Candidate<K>) -> (Outcome, Explanation<K>) {
+        let evaluation = self
+            .try_assess_evaluated(&candidate.rec, candidate.ver, Gather::Intersections)
+            .unwrap_or_else(|err| panic!("{}", err));
+        let explanation = Explanation {
+            rule: evaluation.rule,
+            intersections: evaluation.intersections,
+        };
+        (evaluation.outcome, explanation)
+    }
+
+    pub fn discard(&mut self, entry: TruncatedEntry<K>) {
+        self.try_discard(entry).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    // Discards the items learned up to the entry's version. The tables vacate them by version, so
+    // the entry's keys are only checked against the tables in debug builds, where an entry claiming
+    // a version beyond the one its keys were learned at is rejected whole; validate() finds what
+    // such an entry leaves behind in any build.
+    pub fn try_discard(&mut self, entry: TruncatedEntry<K>) -> Result<(), ExaminerError> {
+        if self.base == 0 {
+            return Err(ExaminerError::Uninitialized);
+        }
+        if entry.ver < self.forced_base {
+            // the entry's items were forgotten when the base was forced past it
+            return Ok(());
+        }
+        if entry.ver < self.base {
+            return Err(ExaminerError::Nonmonotonic {
+                ver: entry.ver,
+                base: self.base,
+            });
+        }
+        // in debug builds, validate the entry in full before removing any of its items; the tables
+        // then discard the keys learned up to the entry's version without looking them up
+        let learned = self.unlearned.binary_search(&entry.ver).is_err();
+        if learned && cfg!(debug_assertions) {
+            Self::check_items(|key| self.reads.get(key).copied(), &entry.readset, entry.ver)?;
+            Self::check_items(|key| self.writes.get(key).copied(), &entry.writeset, entry.ver)?;
+            Self::check_items(|key| self.commutes.get(key).copied(), &entry.writeset, entry.ver)?;
+        }
+        let ancestors_of = |keys: &[K]| match learned {
+            true => keys.iter().flat_map(|key| self.ancestors(key)).collect::<Vec<_>>(),
+            false => vec![],
+        };
+        let (read_ancestors, write_ancestors) = (ancestors_of(&entry.readset), ancestors_of(&entry.writeset));
+        if cfg!(debug_assertions) {
+            Self::check_items(|key| self.read_intents.get(key).copied(), &read_ancestors, entry.ver)?;
+            Self::check_items(|key| self.write_intents.get(key).copied(), &write_ancestors, entry.ver)?;
+        }
+
+        if let Some(ordered_writes) = &mut self.ordered_writes {
+            Self::remove_ordered_items(ordered_writes, &entry.writeset, entry.ver);
+        }
+        self.reads.discard_through(entry.ver);
+        self.writes.discard_through(entry.ver);
+        if !self.commutes.is_empty() {
+            self.commutes.discard_through(entry.ver);
+        }
+        Self::remove_items(&mut self.read_intents, read_ancestors, entry.ver);
+        Self::remove_items(&mut self.write_intents, write_ancestors, entry.ver);
+        while let Some((scan_ver, _)) = self.scans.front() {
+            if *scan_ver > entry.ver {
+                break;
+            }
+            self.scans.pop_front();
+        }
+        Self::retire_through(&mut self.uncertain, entry.ver);
+        Self::retire_through(&mut self.unlearned, entry.ver);
         self.base = entry.ver + 1;
+        self.discards += 1;
+        Ok(())
+    }
+
+    // a shard's part of assess(), for the sharded examiner to combine across shards
+    fn examine_slice(&mut self, slice: Slice<K>) -> Verdict {
+        let mut verdict = Verdict::default();
+        for (read_index, read) in &slice.reads {
+            if let Some(&self_write) = self.writes.get(read) {
+                if self_write > slice.snapshot && !slice.readvers.contains(&self_write) {
+                    if verdict.antidependency.is_none() {
+                        verdict.antidependency = Some((*read_index, self_write));
+                    }
+                } else if self_write > verdict.read_safepoint {
+                    verdict.read_safepoint = self_write;
+                }
+            }
+        }
+
+        // update safepoint for read-write and write-write intersection, and learn the writes
+        for write in &slice.writes {
+            let self_read = self.reads.get(write).copied();
+            let self_write = self.learn_write(write, slice.ver);
+            verdict.write_safepoint = self_read.into_iter().chain(self_write).fold(verdict.write_safepoint, std::cmp::max);
+        }
+        for (_, read) in slice.reads {
+            self.reads.insert_owned(read, slice.ver);
+        }
+        verdict
+    }
+
+    // a shard's part of discard(); the sharded examiner has validated the entry's versions
+    fn discard_slice(&mut self, slice: Slice<K>) {
+        self.reads.discard_through(slice.ver);
+        self.writes.discard_through(slice.ver);
     }
 
     pub fn base(&self) -> Option<u64> {
@@ -266,9 +1736,63 @@ impl Examiner {
         }
     }
 
+    pub fn digest(&self) -> Digest {
+        self.digest
+    }
+
+    // how the examiner learned a version it assessed, to be recorded in the suffix; versions it
+    // has not assessed, or has discarded, are reported as learned with certainty
+    pub fn learning(&self, ver: u64) -> Learning {
+        if self.unlearned.binary_search(&ver).is_ok() {
+            Learning::Unlearned
+        } else if self.uncertain.binary_search(&ver).is_ok() {
+            Learning::Uncertain
+        } else {
+            Learning::Certain
+        }
+    }
+
+    pub fn metrics(&self) -> ExaminerMetrics {
+        ExaminerMetrics {
+            base: self.base(),
+            reads: self.reads.len(),
+            writes: self.writes.len(),
+            scans: self.scans.len(),
+            outcomes: self.outcomes.clone(),
+            discards: self.discards,
+            forced_advances: self.forced_advances,
+            intents: self.read_intents.len() + self.write_intents.len(),
+            commutes: self.commutes.len(),
+        }
+    }
+
+    fn retire_through(vers: &mut VecDeque<u64>, ver: u64) {
+        while matches!(vers.front(), Some(&front) if front <= ver) {
+            vers.pop_front();
+        }
+    }
+
+    fn check_items(
+        existing_ver: impl Fn(&K) -> Option<u64>,
+        items_to_remove: &[K],
+        ver_to_remove: u64,
+    ) -> Result<(), ExaminerError> {
+        for item_to_remove in items_to_remove {
+            if let Some(existing) = existing_ver(item_to_remove) {
+                if existing < ver_to_remove {
+                    return Err(ExaminerError::SkippedVersion {
+                        existing,
+                        ver: ver_to_remove,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn remove_items(
-        existing_items: &mut FxHashMap<String, u64>,
-        items_to_remove: Vec<String>,
+        existing_items: &mut FxHashMap<K, u64>,
+        items_to_remove: Vec<K>,
         ver_to_remove: u64,
     ) {
         for item_to_remove in items_to_remove {
@@ -289,11 +1813,207 @@ impl Examiner {
             }
         }
     }
+
+    fn remove_ordered_items(
+        existing_items: &mut BTreeMap<K, u64>,
+        items_to_remove: &[K],
+        ver_to_remove: u64,
+    ) {
+        for item_to_remove in items_to_remove {
+            if existing_items.get(item_to_remove) == Some(&ver_to_remove) {
+                existing_items.remove(item_to_remove);
+            }
+        }
+    }
 }
 
-impl Default for Examiner {
-    fn default() -> Self {
-        Self::new()
+const CHECKPOINT_MAGIC: &[u8; 4] = b"STXE";
+
+impl<K: Key + Codec> Examiner<K> {
+    // a compact image of the examiner's learned state, including the votes awaiting their outcome,
+    // from which an identically behaving examiner can be restored; metrics are not carried over,
+    // nor is the hierarchy, which is re-enabled by calling with_hierarchy() on the restored examiner
+    pub fn checkpoint(&self) -> Vec<u8> {
+        let mut writer = Writer::new(CHECKPOINT_MAGIC);
+        writer.varint(self.base);
+        writer.u8(match self.isolation {
+            Serializable => 0,
+            Snapshot => 1,
+        });
+        writer.varint(self.max_keys.unwrap_or(0) as u64);
+        writer.varint(self.forced_base);
+        writer.varint(self.digest.ver);
+        writer.varint(self.digest.value);
+        Self::encode_items(&self.reads, &mut writer);
+        Self::encode_items(&self.writes, &mut writer);
+        Self::encode_items(&self.commutes, &mut writer);
+        writer.length(self.scans.len());
+        for (ver, scan) in &self.scans {
+            writer.varint(*ver);
+            scan.encode(&mut writer);
+        }
+        writer.u8(self.precise as u8);
+        for vers in &[&self.uncertain, &self.unlearned] {
+            writer.length(vers.len());
+            for ver in vers.iter() {
+                writer.varint(*ver);
+            }
+        }
+        writer.length(self.provisional.len());
+        for provisional in &self.provisional {
+            writer.varint(provisional.ver);
+            Self::encode_priors(&provisional.reads, &mut writer);
+            Self::encode_priors(&provisional.writes, &mut writer);
+            Self::encode_priors(&provisional.commutes, &mut writer);
+            Self::encode_priors(&provisional.read_intents, &mut writer);
+            Self::encode_priors(&provisional.write_intents, &mut writer);
+        }
+        writer.finish()
+    }
+
+    pub fn restore(checkpoint: &[u8]) -> Result<Self, CheckpointError> {
+        let mut reader = Reader::new(checkpoint, CHECKPOINT_MAGIC)?;
+        let base = reader.varint()?;
+        let isolation = match reader.u8()? {
+            0 => Serializable,
+            1 => Snapshot,
+            tag => return Err(CheckpointError::InvalidTag(tag)),
+        };
+        let max_keys = match reader.varint()? {
+            0 => None,
+            max_keys => Some(usize::try_from(max_keys).map_err(|_| CheckpointError::Overflow)?),
+        };
+        let forced_base = reader.varint()?;
+        let digest = Digest {
+            ver: reader.varint()?,
+            value: reader.varint()?,
+        };
+        let reads = Self::decode_items(&mut reader)?;
+        let writes = Self::decode_items(&mut reader)?;
+        let commutes = Self::decode_items(&mut reader)?;
+        let len = reader.length()?;
+        let mut scans = VecDeque::with_capacity(len);
+        for _ in 0..len {
+            let ver = reader.varint()?;
+            scans.push_back((ver, Scan::decode(&mut reader)?));
+        }
+        let mut decode_flag = || match reader.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            tag => Err(CheckpointError::InvalidTag(tag)),
+        };
+        let precise = decode_flag()?;
+        let mut decode_vers = || -> Result<VecDeque<u64>, CheckpointError> {
+            let len = reader.length()?;
+            let mut vers = VecDeque::with_capacity(len);
+            for _ in 0..len {
+                vers.push_back(reader.varint()?);
+            }
+            Ok(vers)
+        };
+        let uncertain = decode_vers()?;
+        let unlearned = decode_vers()?;
+        let len = reader.length()?;
+        let mut provisional = VecDeque::with_capacity(len);
+        for _ in 0..len {
+            provisional.push_back(Provisional {
+                ver: reader.varint()?,
+                reads: Self::decode_priors(&mut reader)?,
+                writes: Self::decode_priors(&mut reader)?,
+                commutes: Self::decode_priors(&mut reader)?,
+                read_intents: Self::decode_priors(&mut reader)?,
+                write_intents: Self::decode_priors(&mut reader)?,
+            });
+        }
+        reader.finish()?;
+
+        Ok(Examiner {
+            reads,
+            writes,
+            ordered_writes: None,
+            scans,
+            base,
+            isolation,
+            outcomes: OutcomeCounts::default(),
+            discards: 0,
+            max_keys,
+            forced_base,
+            forced_advances: 0,
+            parent: None,
+            read_intents: FxHashMap::default(),
+            write_intents: FxHashMap::default(),
+            commutes,
+            refresh_hints: false,
+            digest,
+            provisional,
+            precise,
+            uncertain,
+            unlearned,
+        })
+    }
+
+    fn encode_items(items: &KeyTable<K>, writer: &mut Writer) {
+        writer.length(items.len());
+        for (key, &ver) in items.iter() {
+            key.encode(writer);
+            writer.varint(ver);
+        }
+    }
+
+    // versions start at 1, so a prior of 0 stands for none
+    fn encode_priors(priors: &[(K, Option<u64>)], writer: &mut Writer) {
+        writer.length(priors.len());
+        for (key, prior) in priors {
+            key.encode(writer);
+            writer.varint(prior.unwrap_or(0));
+        }
+    }
+
+    fn decode_priors(reader: &mut Reader) -> Result<Vec<(K, Option<u64>)>, CheckpointError> {
+        let len = reader.length()?;
+        let mut priors = Vec::with_capacity(len);
+        for _ in 0..len {
+            let key = K::decode(reader)?;
+            let prior = match reader.varint()? {
+                0 => None,
+                prior => Some(prior),
+            };
+            priors.push((key, prior));
+        }
+        Ok(priors)
+    }
+
+    fn decode_items(reader: &mut Reader) -> Result<KeyTable<K>, CheckpointError> {
+        let len = reader.length()?;
+        let mut items = Vec::with_capacity(len);
+        for _ in 0..len {
+            let key = K::decode(reader)?;
+            items.push((key, reader.varint()?));
+        }
+        Ok(items.into_iter().collect())
+    }
+}
+
+impl Examiner<Sym> {
+    // discards a truncated entry whose keys were interned, releasing the entry's references so
+    // that keys no longer retained by the suffix are dropped from the interner
+    pub fn discard_interned<K: Key>(&mut self, entry: TruncatedEntry<Sym>, interner: &mut Interner<K>) {
+        self.try_discard_interned(entry, interner)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    // as discard_interned(), but the references are only released once the entry is discarded,
+    // and the entry is only discarded if all of its references can be released
+    pub fn try_discard_interned<K: Key>(
+        &mut self,
+        entry: TruncatedEntry<Sym>,
+        interner: &mut Interner<K>,
+    ) -> Result<(), ExaminerError> {
+        let syms = [&entry.readset[..], &entry.writeset[..]].concat();
+        interner.check_release_all(&syms).map_err(ExaminerError::Interner)?;
+        self.try_discard(entry)?;
+        interner.release_all(&syms);
+        Ok(())
     }
 }
 
@@ -0,0 +1,334 @@
+use crate::examiner::AbortReason::{Antidependency, Staleness};
+use crate::examiner::Discord::{Assertive, Permissive};
+use crate::examiner::Outcome::{Abort, Commit};
+use crate::examiner::{Candidate, Examiner, ExaminerError, Key, Outcome};
+use crate::sortedvec::SortedVec;
+use crate::suffix::TruncatedEntry;
+use rustc_hash::FxHasher;
+use std::hash::Hasher;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread::JoinHandle;
+
+// An examiner that partitions its read and write tables by key hash across worker threads.
+//
+// Learning is independent of the outcome -- an aborted candidate's reads and writes are learned
+// just the same as a committed one's -- so each shard evolves exactly as the corresponding subset
+// of a sequential examiner's tables would. Per candidate, a shard reports the first antidependency
+// among its reads (by position in the readset) and its contribution to the safepoint; verdicts
+// are then combined in readset order, yielding the sequential examiner's outcome.
+pub struct ShardedExaminer<K: Key + Send + 'static> {
+    workers: Vec<Worker<K>>,
+    base: u64,
+}
+
+struct Worker<K> {
+    requests: Option<Sender<Request<K>>>,
+    verdicts: Receiver<Vec<(usize, Verdict)>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+enum Request<K> {
+    Assess(Vec<(usize, Slice<K>)>),
+    Discard(Vec<Slice<K>>),
+}
+
+// the part of a candidate (or a truncated entry) whose keys fall within a shard
+pub(super) struct Slice<K> {
+    pub(super) ver: u64,
+    pub(super) reads: Vec<(usize, K)>, // reads paired with their position in the readset
+    pub(super) writes: Vec<K>,
+    pub(super) readvers: SortedVec<u64>,
+    pub(super) snapshot: u64,
+}
+
+impl<K> Slice<K> {
+    fn get_or_insert(slice: &mut Option<Slice<K>>, ver: u64, snapshot: u64) -> &mut Slice<K> {
+        slice.get_or_insert_with(|| Slice {
+            ver,
+            reads: vec![],
+            writes: vec![],
+            readvers: SortedVec::default(),
+            snapshot,
+        })
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+pub(super) struct Verdict {
+    pub(super) antidependency: Option<(usize, u64)>, // the first conflicting read's position and writer
+    pub(super) read_safepoint: u64,                  // the highest write-read intersection
+    pub(super) write_safepoint: u64,                 // the highest read-write or write-write intersection
+}
+
+fn run_worker<K: Key>(requests: Receiver<Request<K>>, verdicts: Sender<Vec<(usize, Verdict)>>) {
+    let mut shard = Examiner::default();
+    for request in requests {
+        match request {
+            Request::Assess(slices) => {
+                let batch_verdicts = slices
+                    .into_iter()
+                    .map(|(candidate_index, slice)| (candidate_index, shard.examine_slice(slice)))
+                    .collect();
+                if verdicts.send(batch_verdicts).is_err() {
+                    return;
+                }
+            }
+            Request::Discard(slices) => {
+                for slice in slices {
+                    shard.discard_slice(slice);
+                }
+            }
+        }
+    }
+}
+
+impl<K: Key + Send + 'static> ShardedExaminer<K> {
+    pub fn new(num_shards: usize) -> Self {
+        assert!(num_shards > 0, "invalid num_shards ({})", num_shards);
+        let workers = (0..num_shards)
+            .map(|shard_index| {
+                let (request_tx, request_rx) = channel();
+                let (verdict_tx, verdict_rx) = channel();
+                let handle = std::thread::Builder::new()
+                    .name(format!("examiner-shard-{}", shard_index))
+                    .spawn(move || run_worker(request_rx, verdict_tx))
+                    .unwrap();
+                Worker {
+                    requests: Some(request_tx),
+                    verdicts: verdict_rx,
+                    handle: Some(handle),
+                }
+            })
+            .collect();
+        Self { workers, base: 0 }
+    }
+
+    pub fn num_shards(&self) -> usize {
+        self.workers.len()
+    }
+
+    pub fn base(&self) -> Option<u64> {
+        match self.base {
+            0 => None,
+            base => Some(base),
+        }
+    }
+
+    fn shard_of(&self, key: &K) -> usize {
+        let mut hasher = FxHasher::default();
+        key.hash(&mut hasher);
+        // the low-order bits select buckets within each shard's table, so route on the high-order
+        // bits to avoid clustering
+        ((hasher.finish() >> 32) % self.workers.len() as u64) as usize
+    }
+
+    fn split(
+        &self,
+        ver: u64,
+        readset: Vec<K>,
+        writeset: Vec<K>,
+        readvers: &SortedVec<u64>,
+        snapshot: u64,
+    ) -> Vec<Option<Slice<K>>> {
+        let mut slices = (0..self.workers.len()).map(|_| None).collect::<Vec<_>>();
+        for (read_index, read) in readset.into_iter().enumerate() {
+            let slice = Slice::get_or_insert(&mut slices[self.shard_of(&read)], ver, snapshot);
+            if slice.reads.is_empty() {
+                slice.readvers = readvers.clone();
+            }
+            slice.reads.push((read_index, read));
+        }
+        for write in writeset {
+            let slice = Slice::get_or_insert(&mut slices[self.shard_of(&write)], ver, snapshot);
+            slice.writes.push(write);
+        }
+        slices
+    }
+
+    fn send(&self, shard_index: usize, request: Request<K>) {
+        self.workers[shard_index]
+            .requests
+            .as_ref()
+            .unwrap()
+            .send(request)
+            .expect("shard worker terminated");
+    }
+
+    pub fn assess(&mut self, candidate: Candidate<K>) -> Outcome {
+        self.try_assess(candidate).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_assess(&mut self, candidate: Candidate<K>) -> Result<Outcome, ExaminerError> {
+        Ok(self.try_assess_batch(vec![candidate])?.pop().unwrap())
+    }
+
+    // assesses a run of candidates in log order, yielding the outcomes a sequential examiner would
+    pub fn assess_batch(&mut self, candidates: Vec<Candidate<K>>) -> Vec<Outcome> {
+        self.try_assess_batch(candidates).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    // as assess_batch(), but rejects the whole batch, assessing none of it, if any candidate holds
+    // what the shards cannot certify; keys are routed to shards by hash, so scans and commutative
+    // writes, which would have to meet keys across shards, are unsupported, and keys are never
+    // treated as hierarchical
+    pub fn try_assess_batch(&mut self, candidates: Vec<Candidate<K>>) -> Result<Vec<Outcome>, ExaminerError> {
+        for candidate in &candidates {
+            if candidate.ver == 0 {
+                return Err(ExaminerError::UnsupportedVersion);
+            }
+            if !candidate.rec.scans.is_empty() {
+                return Err(ExaminerError::Unsupported("scans"));
+            }
+            if !candidate.rec.commutes.is_empty() {
+                return Err(ExaminerError::Unsupported("commutative writes"));
+            }
+        }
+
+        let num_shards = self.workers.len();
+        let mut shard_requests = (0..num_shards).map(|_| vec![]).collect::<Vec<_>>();
+        let mut prelims = Vec::with_capacity(candidates.len());
+        for (candidate_index, candidate) in candidates.into_iter().enumerate() {
+            if self.base == 0 {
+                self.base = candidate.ver;
+            }
+            let rec = candidate.rec;
+            prelims.push((rec.readset.is_empty(), rec.snapshot, self.base - 1));
+            let slices = self.split(
+                candidate.ver,
+                rec.readset,
+                rec.writeset,
+                &rec.readvers,
+                rec.snapshot,
+            );
+            for (shard_index, slice) in slices.into_iter().enumerate() {
+                if let Some(slice) = slice {
+                    shard_requests[shard_index].push((candidate_index, slice));
+                }
+            }
+        }
+
+        for (shard_index, slices) in shard_requests.into_iter().enumerate() {
+            self.send(shard_index, Request::Assess(slices));
+        }
+
+        let mut verdicts = vec![Verdict::default(); prelims.len()];
+        for worker in &self.workers {
+            let shard_verdicts = worker.verdicts.recv().expect("shard worker terminated");
+            for (candidate_index, shard_verdict) in shard_verdicts {
+                let verdict = &mut verdicts[candidate_index];
+                verdict.antidependency = match (verdict.antidependency, shard_verdict.antidependency) {
+                    (Some(existing), Some(new)) => Some(std::cmp::min(existing, new)),
+                    (existing, new) => existing.or(new),
+                };
+                verdict.read_safepoint = std::cmp::max(verdict.read_safepoint, shard_verdict.read_safepoint);
+                verdict.write_safepoint = std::cmp::max(verdict.write_safepoint, shard_verdict.write_safepoint);
+            }
+        }
+
+        let outcomes = prelims
+            .into_iter()
+            .zip(verdicts)
+            .map(|((write_only, snapshot, min_safepoint), verdict)| {
+                // rule R1: commit write-only transactions
+                if write_only {
+                    return Commit {
+                        safepoint: std::cmp::max(min_safepoint, verdict.write_safepoint),
+                        discord: Assertive,
+                    };
+                }
+
+                // rule R2: conditionally abort transactions outside the suffix
+                if snapshot < min_safepoint {
+                    return Abort {
+                        reason: Staleness,
+                        discord: Permissive,
+                        refresh: None,
+                    };
+                }
+
+                // rule R3: abort on antidependency
+                if let Some((_, self_write)) = verdict.antidependency {
+                    return Abort {
+                        reason: Antidependency(self_write),
+                        discord: Assertive,
+                        refresh: None,
+                    };
+                }
+
+                // rule R4 conditionally commit
+                let safepoint = [min_safepoint, verdict.read_safepoint, verdict.write_safepoint]
+                    .iter()
+                    .copied()
+                    .max()
+                    .unwrap();
+                Commit {
+                    safepoint,
+                    discord: Permissive,
+                }
+            })
+            .collect();
+        Ok(outcomes)
+    }
+
+    pub fn discard(&mut self, entry: TruncatedEntry<K>) {
+        self.try_discard(entry).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_discard(&mut self, entry: TruncatedEntry<K>) -> Result<(), ExaminerError> {
+        self.try_discard_all(vec![entry])
+    }
+
+    pub fn discard_all(&mut self, entries: Vec<TruncatedEntry<K>>) {
+        self.try_discard_all(entries).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    // as discard_all(), but discards none of the entries if any is out of order
+    pub fn try_discard_all(&mut self, entries: Vec<TruncatedEntry<K>>) -> Result<(), ExaminerError> {
+        if self.base == 0 {
+            return Err(ExaminerError::Uninitialized);
+        }
+        let mut base = self.base;
+        for entry in &entries {
+            if entry.ver < base {
+                return Err(ExaminerError::Nonmonotonic { ver: entry.ver, base });
+            }
+            base = entry.ver + 1;
+        }
+
+        let num_shards = self.workers.len();
+        let mut shard_requests = (0..num_shards).map(|_| vec![]).collect::<Vec<_>>();
+        for entry in entries {
+            let slices = self.split(entry.ver, entry.readset, entry.writeset, &SortedVec::default(), 0);
+            for (shard_index, slice) in slices.into_iter().enumerate() {
+                if let Some(slice) = slice {
+                    shard_requests[shard_index].push(slice);
+                }
+            }
+        }
+        self.base = base;
+
+        for (shard_index, slices) in shard_requests.into_iter().enumerate() {
+            if !slices.is_empty() {
+                self.send(shard_index, Request::Discard(slices));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<K: Key + Send + 'static> Drop for ShardedExaminer<K> {
+    fn drop(&mut self) {
+        for worker in &mut self.workers {
+            // closing the request channel stops the worker
+            worker.requests.take();
+        }
+        for worker in &mut self.workers {
+            if let Some(handle) = worker.handle.take() {
+                let _ = handle.join();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
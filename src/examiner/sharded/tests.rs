@@ -0,0 +1,141 @@
+use crate::examiner::sharded::ShardedExaminer;
+use crate::examiner::{Candidate, Examiner, ExaminerError, Record, Scan};
+use crate::sortedvec::SortedVec;
+use crate::suffix::{AppendResult, CompleteResult, Suffix, TruncatedEntry};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use uuid::Uuid;
+
+fn random_candidate(rng: &mut StdRng, items: &[String], ver: u64) -> Candidate {
+    let readset = (0..rng.gen_range(0..4))
+        .map(|_| items[rng.gen_range(0..items.len())].clone())
+        .collect();
+    let writeset = (0..rng.gen_range(0..4))
+        .map(|_| items[rng.gen_range(0..items.len())].clone())
+        .collect();
+    let snapshot = ver - 1 - rng.gen_range(0..std::cmp::min(ver, 6));
+    let readvers = (snapshot + 1..ver)
+        .filter(|_| rng.gen_bool(0.3))
+        .collect::<Vec<_>>();
+    Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(ver as u128),
+            readset,
+            writeset,
+            readvers: readvers.into(),
+            snapshot,
+            ..Default::default()
+        },
+        ver,
+    }
+}
+
+#[test]
+fn differential_against_sequential() {
+    for seed in 0..20 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let num_items = rng.gen_range(1..16);
+        let items = (0..num_items).map(|i| format!("item-{}", i)).collect::<Vec<_>>();
+        let extent = rng.gen_range(1..8);
+        let mut suffix = Suffix::default();
+        let mut sequential = Examiner::new();
+        let mut sharded = ShardedExaminer::new(rng.gen_range(1..5));
+        let mut ver = 1;
+        for _ in 0..40 {
+            let batch = (0..rng.gen_range(1..10))
+                .map(|_| {
+                    let candidate = random_candidate(&mut rng, &items, ver);
+                    ver += 1;
+                    candidate
+                })
+                .collect::<Vec<_>>();
+
+            let expected = batch
+                .iter()
+                .map(|candidate| {
+                    let result = suffix.append(
+                        candidate.rec.readset.clone(),
+                        candidate.rec.writeset.clone(),
+                        candidate.ver,
+                    );
+                    assert_eq!(AppendResult::Appended, result);
+                    sequential.assess(candidate.clone())
+                })
+                .collect::<Vec<_>>();
+            let batch_vers = batch.iter().map(|candidate| candidate.ver).collect::<Vec<_>>();
+            let outcomes = sharded.assess_batch(batch);
+            assert_eq!(expected, outcomes, "seed {}", seed);
+
+            for batch_ver in batch_vers {
+                assert!(matches!(suffix.complete(batch_ver), CompleteResult::Completed(_)));
+            }
+            if let Some(truncated) = suffix.truncate(extent, extent) {
+                let truncated = truncated.collect::<Vec<_>>();
+                for entry in &truncated {
+                    sequential.discard(entry.clone());
+                }
+                sharded.discard_all(truncated);
+                assert_eq!(sequential.base(), sharded.base());
+            }
+        }
+    }
+}
+
+#[test]
+fn assess_single() {
+    let mut sharded = ShardedExaminer::new(3);
+    assert_eq!(3, sharded.num_shards());
+    assert_eq!(None, sharded.base());
+    let outcome = sharded.assess(Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(1),
+            readset: vec![],
+            writeset: vec!["x".to_string()],
+            readvers: SortedVec::default(),
+            snapshot: 0,
+            ..Default::default()
+        },
+        ver: 1,
+    });
+    assert!(outcome.is_commit());
+    assert_eq!(Some(1), sharded.base());
+}
+
+#[test]
+fn assess_unsupported() {
+    let candidate = |scans, commutes, ver| Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(ver as u128),
+            readset: vec![],
+            scans,
+            writeset: vec!["x".to_string()],
+            commutes,
+            readvers: SortedVec::default(),
+            snapshot: 0,
+        },
+        ver,
+    };
+    let mut sharded = ShardedExaminer::new(2);
+    let scan = candidate(vec![Scan::prefix("x")], vec![], 1);
+    assert_eq!(Err(ExaminerError::Unsupported("scans")), sharded.try_assess(scan));
+    let commute = candidate(vec![], vec!["y".to_string()], 1);
+    assert_eq!(Err(ExaminerError::Unsupported("commutative writes")), sharded.try_assess(commute));
+    assert_eq!(Err(ExaminerError::UnsupportedVersion), sharded.try_assess(candidate(vec![], vec![], 0)));
+
+    // a batch is rejected as a whole, leaving the examiner uninitialized
+    let batch = vec![candidate(vec![], vec![], 1), candidate(vec![], vec!["y".to_string()], 2)];
+    assert!(sharded.try_assess_batch(batch).is_err());
+    assert_eq!(None, sharded.base());
+    let entry = |ver| TruncatedEntry { ver, readset: vec![], writeset: vec!["x".to_string()] };
+    assert_eq!(Err(ExaminerError::Uninitialized), sharded.try_discard(entry(1)));
+
+    assert!(sharded.assess(candidate(vec![], vec![], 1)).is_commit());
+    assert!(sharded.assess(candidate(vec![], vec![], 2)).is_commit());
+    assert_eq!(
+        Err(ExaminerError::Nonmonotonic { ver: 1, base: 2 }),
+        sharded.try_discard_all(vec![entry(1), entry(1)])
+    );
+    assert_eq!(Some(1), sharded.base());
+    assert_eq!(Ok(()), sharded.try_discard_all(vec![entry(1), entry(2)]));
+    assert_eq!(Some(3), sharded.base());
+}
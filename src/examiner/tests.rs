@@ -1,13 +1,20 @@
-use crate::examiner::{Examiner, Record, Candidate};
+use crate::examiner::{Examiner, Record, Candidate, Key, Scan, Outcome, AbortDetail, Explanation, Rule, ExaminerError, ExaminerMetrics, OutcomeCounts, DiscordCounts, Refresh, Digest, Discrepancy, Table, Learning};
+use crate::examiner::Intersection::{ReadWrite, WriteRead, WriteWrite, ScanWrite, WriteScan};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::ops::Bound;
 use crate::examiner::Discord::{Permissive, Assertive};
 use crate::examiner::Outcome::{Commit, Abort};
 use uuid::Uuid;
-use crate::suffix::TruncatedEntry;
-use crate::examiner::AbortReason::{Staleness, Antidependency};
+use crate::suffix::{Suffix, TruncatedEntry};
+use crate::examiner::AbortReason::{Staleness, Antidependency, Phantom, WriteConflict};
+use crate::examiner::Isolation::{Serializable, Snapshot};
 use crate::sortedvec::SortedVec;
+use crate::checkpoint::CheckpointError;
+use crate::interner::{Interner, InternerError};
 
-impl Examiner {
-    fn knows(&self, candidate: &Candidate) -> bool {
+impl<K: Key> Examiner<K> {
+    fn knows(&self, candidate: &Candidate<K>) -> bool {
         for read in candidate.rec.readset.iter() {
             match self.reads.get(read) {
                 Some(&ver) if ver >= candidate.ver => {}
@@ -24,8 +31,8 @@ impl Examiner {
     }
 }
 
-impl Candidate {
-    fn truncated(&self) -> TruncatedEntry {
+impl<K: Key> Candidate<K> {
+    fn truncated(&self) -> TruncatedEntry<K> {
         TruncatedEntry {
             readset: self.rec.readset.clone(),
             writeset: self.rec.writeset.clone(),
@@ -45,6 +52,7 @@ fn learn_discard() {
             writeset: vec!["y".into()],
             readvers: SortedVec::default(),
             snapshot: 0,
+            ..Default::default()
         },
         ver: 5,
     };
@@ -69,6 +77,7 @@ fn learn_discard_two_with_identical_items() {
             writeset: vec!["y".into()],
             readvers: SortedVec::default(),
             snapshot: 0,
+            ..Default::default()
         },
         ver: 1,
     };
@@ -80,6 +89,7 @@ fn learn_discard_two_with_identical_items() {
             writeset: vec!["y".into()],
             readvers: SortedVec::default(),
             snapshot: 0,
+            ..Default::default()
         },
         ver: 2,
     };
@@ -111,6 +121,7 @@ fn learn_discard_two_with_nonidentical_items() {
             writeset: vec!["x".into(), "y".into()],
             readvers: SortedVec::default(),
             snapshot: 0,
+            ..Default::default()
         },
         ver: 1,
     };
@@ -122,6 +133,7 @@ fn learn_discard_two_with_nonidentical_items() {
             writeset: vec!["x".into(), "y".into()],
             readvers: SortedVec::default(),
             snapshot: 0,
+            ..Default::default()
         },
         ver: 2,
     };
@@ -160,6 +172,7 @@ fn discard_nonmonotonic() {
             writeset: vec![],
             readvers: SortedVec::default(),
             snapshot: 0,
+            ..Default::default()
         },
         ver: 2,
     });
@@ -170,6 +183,90 @@ fn discard_nonmonotonic() {
     });
 }
 
+#[test]
+fn try_discard_errors() {
+    let mut examiner = Examiner::new();
+    let entry = |ver: u64, writeset: Vec<String>| TruncatedEntry {
+        ver,
+        readset: vec![],
+        writeset,
+    };
+    assert_eq!(Err(ExaminerError::Uninitialized), examiner.try_discard(entry(1, vec![])));
+
+    let candidate = |ver: u64| Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(ver as u128),
+            readset: vec!["x".into()],
+            writeset: vec!["y".into()],
+            readvers: SortedVec::default(),
+            snapshot: ver - 1,
+            ..Default::default()
+        },
+        ver,
+    };
+    let (c2, c3) = (candidate(2), candidate(3));
+    assert_eq!(Ok(()), examiner.try_learn(c2.clone()));
+    assert_eq!(Ok(()), examiner.try_learn(c3.clone()));
+    assert_eq!(
+        Err(ExaminerError::Nonmonotonic { ver: 1, base: 2 }),
+        examiner.try_discard(entry(1, vec![]))
+    );
+
+    // an entry claiming a version beyond the one learned is rejected whole in debug builds, leaving
+    // 'x' in place
+    if !cfg!(debug_assertions) {
+        return;
+    }
+    let err = examiner.try_discard(TruncatedEntry {
+        ver: 4,
+        readset: vec!["x".into()],
+        writeset: vec!["y".into(), "z".into()],
+    }).unwrap_err();
+    assert_eq!(ExaminerError::SkippedVersion { existing: 3, ver: 4 }, err);
+    assert_eq!("skipped version 3 while trying to remove 4", err.to_string());
+    assert_eq!(Some(2), examiner.base());
+    assert_knows(&examiner, &c3);
+
+    // as is one claiming a version that was learned, but not for its keys
+    examiner.learn(Candidate {
+        rec: Record { writeset: vec!["z".into()], ..candidate(5).rec },
+        ver: 5,
+    });
+    let err = examiner.try_discard(entry(4, vec!["y".into()])).unwrap_err();
+    assert_eq!(ExaminerError::SkippedVersion { existing: 3, ver: 4 }, err);
+    assert_eq!(Some(2), examiner.base());
+    assert_knows(&examiner, &c3);
+
+    assert_eq!(Ok(()), examiner.try_discard(c2.truncated()));
+    assert_eq!(Ok(()), examiner.try_discard(c3.truncated()));
+    assert!(!examiner.knows(&c3));
+}
+
+#[test]
+fn try_assess_learn_ver_0() {
+    let mut examiner = Examiner::new();
+    let candidate = Candidate {
+        rec: Record {
+            xid: Uuid::default(),
+            readset: vec![],
+            writeset: vec!["x".into()],
+            readvers: SortedVec::default(),
+            snapshot: 0,
+            ..Default::default()
+        },
+        ver: 0,
+    };
+    assert_eq!(Err(ExaminerError::UnsupportedVersion), examiner.try_assess(candidate.clone()));
+    assert_eq!(Err(ExaminerError::UnsupportedVersion), examiner.try_learn(candidate.clone()));
+    assert_eq!(None, examiner.base());
+
+    let candidate = Candidate { ver: 1, ..candidate };
+    assert_eq!(
+        Ok(Commit {safepoint: 0, discord: Assertive}),
+        examiner.try_assess(candidate)
+    );
+}
+
 #[test] #[should_panic(expected = "unsupported version 0")]
 fn learn_ver_0() {
     Examiner::new().learn(Candidate {
@@ -179,6 +276,7 @@ fn learn_ver_0() {
             writeset: vec![],
             readvers: SortedVec::default(),
             snapshot: 0,
+            ..Default::default()
         },
         ver: 0,
     });
@@ -193,6 +291,7 @@ fn assess_ver_0() {
             writeset: vec![],
             readvers: SortedVec::default(),
             snapshot: 0,
+            ..Default::default()
         },
         ver: 0,
     });
@@ -209,6 +308,7 @@ fn paper_example_1() {
             writeset: vec!["x".into(), "y".into()],
             readvers: SortedVec::default(),
             snapshot: 0,
+            ..Default::default()
         },
         ver: 4,
     });
@@ -219,6 +319,7 @@ fn paper_example_1() {
             writeset: vec![],
             readvers: vec![4].into(),
             snapshot: 0,
+            ..Default::default()
         },
         ver: 5,
     });
@@ -229,6 +330,7 @@ fn paper_example_1() {
             writeset: vec!["x".into(), "y".into()],
             readvers: SortedVec::default(),
             snapshot: 4,
+            ..Default::default()
         },
         ver: 6,
     };
@@ -238,6 +340,56 @@ fn paper_example_1() {
     assert_knows(&examiner, &candidate)
 }
 
+#[test]
+fn paper_example_1_explained() {
+    let mut examiner = Examiner::new();
+    examiner.base = 4;
+    examiner.learn(Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(1),
+            readset: vec!["x".into(), "y".into()],
+            writeset: vec!["x".into(), "y".into()],
+            readvers: SortedVec::default(),
+            snapshot: 0,
+            ..Default::default()
+        },
+        ver: 4,
+    });
+    examiner.learn(Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(2),
+            readset: vec!["x".into(), "y".into()],
+            writeset: vec![],
+            readvers: vec![4].into(),
+            snapshot: 0,
+            ..Default::default()
+        },
+        ver: 5,
+    });
+    let candidate = Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(3),
+            readset: vec![],
+            writeset: vec!["x".into(), "y".into()],
+            readvers: SortedVec::default(),
+            snapshot: 4,
+            ..Default::default()
+        },
+        ver: 6,
+    };
+    assert!(!examiner.knows(&candidate));
+    let (outcome, explanation) = examiner.assess_explained(candidate.clone());
+    assert_eq!(Commit {safepoint: 5, discord: Assertive}, outcome);
+    assert_eq!(Explanation {
+        rule: Rule::R1,
+        intersections: vec![
+            (ReadWrite, "x".into(), 5), (WriteWrite, "x".into(), 4),
+            (ReadWrite, "y".into(), 5), (WriteWrite, "y".into(), 4),
+        ]
+    }, explanation);
+    assert_knows(&examiner, &candidate)
+}
+
 #[test]
 fn paper_example_2() {
     let mut examiner = Examiner::new();
@@ -249,6 +401,7 @@ fn paper_example_2() {
             writeset: vec!["x".into(), "y".into()],
             readvers: SortedVec::default(),
             snapshot: 11,
+            ..Default::default()
         },
         ver: 12,
     });
@@ -259,6 +412,7 @@ fn paper_example_2() {
             writeset: vec![],
             readvers: SortedVec::default(),
             snapshot: 12,
+            ..Default::default()
         },
         ver: 13,
     });
@@ -269,6 +423,7 @@ fn paper_example_2() {
             writeset: vec!["x".into(), "y".into()],
             readvers: SortedVec::default(),
             snapshot: 5,
+            ..Default::default()
         },
         ver: 14,
     });
@@ -279,12 +434,68 @@ fn paper_example_2() {
             writeset: vec!["z".into()],
             readvers: SortedVec::default(),
             snapshot: 10,
+            ..Default::default()
         },
         ver: 15,
     };
     assert!(!examiner.knows(&candidate));
     let outcome = examiner.assess(candidate.clone());
-    assert_eq!(Abort {reason: Staleness, discord: Permissive}, outcome);
+    assert_eq!(Abort {reason: Staleness, discord: Permissive, refresh: None}, outcome);
+    assert_knows(&examiner, &candidate)
+}
+
+#[test]
+fn paper_example_2_explained() {
+    let mut examiner = Examiner::new();
+    examiner.base = 12;
+    examiner.learn(Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(1),
+            readset: vec!["x".into(), "y".into()],
+            writeset: vec!["x".into(), "y".into()],
+            readvers: SortedVec::default(),
+            snapshot: 11,
+            ..Default::default()
+        },
+        ver: 12,
+    });
+    examiner.learn(Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(2),
+            readset: vec!["x".into(), "y".into()],
+            writeset: vec![],
+            readvers: SortedVec::default(),
+            snapshot: 12,
+            ..Default::default()
+        },
+        ver: 13,
+    });
+    examiner.learn(Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(3),
+            readset: vec![],
+            writeset: vec!["x".into(), "y".into()],
+            readvers: SortedVec::default(),
+            snapshot: 5,
+            ..Default::default()
+        },
+        ver: 14,
+    });
+    let candidate = Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(4),
+            readset: vec!["v".into(), "w".into()],
+            writeset: vec!["z".into()],
+            readvers: SortedVec::default(),
+            snapshot: 10,
+            ..Default::default()
+        },
+        ver: 15,
+    };
+    assert!(!examiner.knows(&candidate));
+    let (outcome, explanation) = examiner.assess_explained(candidate.clone());
+    assert_eq!(Abort {reason: Staleness, discord: Permissive, refresh: None}, outcome);
+    assert_eq!(Explanation {rule: Rule::R2, intersections: vec![]}, explanation);
     assert_knows(&examiner, &candidate)
 }
 
@@ -299,6 +510,7 @@ fn paper_example_3() {
             writeset: vec![],
             readvers: SortedVec::default(),
             snapshot: 19,
+            ..Default::default()
         },
         ver: 24,
     });
@@ -309,6 +521,7 @@ fn paper_example_3() {
             writeset: vec!["x".into(), "y".into()],
             readvers: SortedVec::default(),
             snapshot: 22,
+            ..Default::default()
         },
         ver: 25,
     });
@@ -319,6 +532,7 @@ fn paper_example_3() {
             writeset: vec!["y".into(), "z".into()],
             readvers: SortedVec::default(),
             snapshot: 25,
+            ..Default::default()
         },
         ver: 26,
     });
@@ -329,6 +543,7 @@ fn paper_example_3() {
             writeset: vec![],
             readvers: SortedVec::default(),
             snapshot: 26,
+            ..Default::default()
         },
         ver: 27,
     });
@@ -339,12 +554,79 @@ fn paper_example_3() {
             writeset: vec!["z".into()],
             readvers: vec![25].into(),
             snapshot: 23,
+            ..Default::default()
         },
         ver: 28,
     };
     assert!(!examiner.knows(&candidate));
     let outcome = examiner.assess(candidate.clone());
-    assert_eq!(Abort {reason: Antidependency(26), discord: Assertive}, outcome);
+    assert_eq!(Abort {reason: Antidependency(26), discord: Assertive, refresh: None}, outcome);
+    assert_knows(&examiner, &candidate)
+}
+
+#[test]
+fn paper_example_3_explained() {
+    let mut examiner = Examiner::new();
+    examiner.base = 24;
+    examiner.learn(Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(1),
+            readset: vec!["x".into(), "y".into()],
+            writeset: vec![],
+            readvers: SortedVec::default(),
+            snapshot: 19,
+            ..Default::default()
+        },
+        ver: 24,
+    });
+    examiner.learn(Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(2),
+            readset: vec!["x".into(), "y".into()],
+            writeset: vec!["x".into(), "y".into()],
+            readvers: SortedVec::default(),
+            snapshot: 22,
+            ..Default::default()
+        },
+        ver: 25,
+    });
+    examiner.learn(Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(3),
+            readset: vec![],
+            writeset: vec!["y".into(), "z".into()],
+            readvers: SortedVec::default(),
+            snapshot: 25,
+            ..Default::default()
+        },
+        ver: 26,
+    });
+    examiner.learn(Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(4),
+            readset: vec!["v".into(), "w".into()],
+            writeset: vec![],
+            readvers: SortedVec::default(),
+            snapshot: 26,
+            ..Default::default()
+        },
+        ver: 27,
+    });
+    let candidate = Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(5),
+            readset: vec!["x".into(), "z".into()],
+            writeset: vec!["z".into()],
+            readvers: vec![25].into(),
+            snapshot: 23,
+            ..Default::default()
+        },
+        ver: 28,
+    };
+    assert!(!examiner.knows(&candidate));
+    let (outcome, explanation) = examiner.assess_explained(candidate.clone());
+    assert_eq!(Abort {reason: Antidependency(26), discord: Assertive, refresh: None}, outcome);
+    assert_eq!(Explanation {rule: Rule::R3, intersections: vec![]}, explanation);
     assert_knows(&examiner, &candidate)
 }
 
@@ -359,6 +641,7 @@ fn paper_example_4() {
             writeset: vec![],
             readvers: SortedVec::default(),
             snapshot: 23,
+            ..Default::default()
         },
         ver: 30,
     });
@@ -369,6 +652,7 @@ fn paper_example_4() {
             writeset: vec!["w".into(), "x".into()],
             readvers: SortedVec::default(),
             snapshot: 24,
+            ..Default::default()
         },
         ver: 31,
     });
@@ -379,6 +663,7 @@ fn paper_example_4() {
             writeset: vec!["y".into()],
             readvers: SortedVec::default(),
             snapshot: 25,
+            ..Default::default()
         },
         ver: 32,
     });
@@ -389,6 +674,7 @@ fn paper_example_4() {
             writeset: vec!["y".into()],
             readvers: SortedVec::default(),
             snapshot: 26,
+            ..Default::default()
         },
         ver: 33,
     });
@@ -399,6 +685,7 @@ fn paper_example_4() {
             writeset: vec!["w".into()],
             readvers: SortedVec::default(),
             snapshot: 31,
+            ..Default::default()
         },
         ver: 34,
     });
@@ -409,6 +696,7 @@ fn paper_example_4() {
             writeset: vec!["z".into()],
             readvers: SortedVec::default(),
             snapshot: 31,
+            ..Default::default()
         },
         ver: 35,
     };
@@ -418,13 +706,93 @@ fn paper_example_4() {
     assert_knows(&examiner, &candidate)
 }
 
-fn assert_knows(examiner: &Examiner, candidate: &Candidate) {
-    if !examiner.knows(&candidate) {
+#[test]
+fn paper_example_4_explained() {
+    let mut examiner = Examiner::new();
+    examiner.base = 30;
+    examiner.learn(Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(1),
+            readset: vec!["x".into(), "y".into()],
+            writeset: vec![],
+            readvers: SortedVec::default(),
+            snapshot: 23,
+            ..Default::default()
+        },
+        ver: 30,
+    });
+    examiner.learn(Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(2),
+            readset: vec!["x".into(), "y".into()],
+            writeset: vec!["w".into(), "x".into()],
+            readvers: SortedVec::default(),
+            snapshot: 24,
+            ..Default::default()
+        },
+        ver: 31,
+    });
+    examiner.learn(Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(3),
+            readset: vec![],
+            writeset: vec!["y".into()],
+            readvers: SortedVec::default(),
+            snapshot: 25,
+            ..Default::default()
+        },
+        ver: 32,
+    });
+    examiner.learn(Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(4),
+            readset: vec!["v".into(), "z".into()],
+            writeset: vec!["y".into()],
+            readvers: SortedVec::default(),
+            snapshot: 26,
+            ..Default::default()
+        },
+        ver: 33,
+    });
+    examiner.learn(Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(5),
+            readset: vec![],
+            writeset: vec!["w".into()],
+            readvers: SortedVec::default(),
+            snapshot: 31,
+            ..Default::default()
+        },
+        ver: 34,
+    });
+    let candidate = Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(6),
+            readset: vec!["x".into(), "z".into()],
+            writeset: vec!["z".into()],
+            readvers: SortedVec::default(),
+            snapshot: 31,
+            ..Default::default()
+        },
+        ver: 35,
+    };
+    assert!(!examiner.knows(&candidate));
+    let (outcome, explanation) = examiner.assess_explained(candidate.clone());
+    assert_eq!(Commit {safepoint: 33, discord: Permissive}, outcome);
+    assert_eq!(Explanation {
+        rule: Rule::R4,
+        intersections: vec![(WriteRead, "x".into(), 31), (ReadWrite, "z".into(), 33)]
+    }, explanation);
+    assert_knows(&examiner, &candidate)
+}
+
+fn assert_knows<K: Key>(examiner: &Examiner<K>, candidate: &Candidate<K>) {
+    if !examiner.knows(candidate) {
         for read in candidate.rec.readset.iter() {
             match examiner.reads.get(read) {
                 Some(&ver) if ver >= candidate.ver => {}
                 _ => panic!(
-                    "{:?} not known to {:?} for read of {}",
+                    "{:?} not known to {:?} for read of {:?}",
                     candidate, examiner, read
                 ),
             }
@@ -433,7 +801,7 @@ fn assert_knows(examiner: &Examiner, candidate: &Candidate) {
             match examiner.writes.get(write) {
                 Some(&ver) if ver >= candidate.ver => {}
                 _ => panic!(
-                    "{:?} not known to {:?} for write of {}",
+                    "{:?} not known to {:?} for write of {:?}",
                     candidate, examiner, write
                 ),
             }
@@ -446,4 +814,1719 @@ fn compress() {
     assert_eq!((vec![].into(), 10), Record::compress(vec![3, 9, 6], 10));
     assert_eq!((vec![6, 9].into(), 4), Record::compress(vec![9, 6, 3], 4));
     assert_eq!((vec![6, 9].into(), 3), Record::compress(vec![3, 6, 9], 1));
-}
\ No newline at end of file
+}
+
+#[test]
+fn assess_u64_keys() {
+    let mut examiner = Examiner::default();
+    examiner.learn(Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(1),
+            readset: vec![],
+            writeset: vec![100u64],
+            readvers: SortedVec::default(),
+            snapshot: 0,
+            ..Default::default()
+        },
+        ver: 1,
+    });
+    let candidate = Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(2),
+            readset: vec![100u64],
+            writeset: vec![200u64],
+            readvers: SortedVec::default(),
+            snapshot: 0,
+            ..Default::default()
+        },
+        ver: 2,
+    };
+    let outcome = examiner.assess(candidate.clone());
+    assert_eq!(Abort {reason: Antidependency(1), discord: Assertive, refresh: None}, outcome);
+    assert!(examiner.knows(&candidate));
+
+    examiner.discard(TruncatedEntry { ver: 1, readset: vec![], writeset: vec![100] });
+    assert_eq!(Some(2), examiner.base());
+    examiner.discard(candidate.truncated());
+    assert!(!examiner.knows(&candidate));
+}
+
+#[test]
+fn assess_composite_keys() {
+    let mut examiner = Examiner::default();
+    examiner.learn(Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(1),
+            readset: vec![("orders", 7u64)],
+            writeset: vec![("orders", 7)],
+            readvers: SortedVec::default(),
+            snapshot: 0,
+            ..Default::default()
+        },
+        ver: 1,
+    });
+    let outcome = examiner.assess(Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(2),
+            readset: vec![("orders", 8)],
+            writeset: vec![("orders", 7)],
+            readvers: SortedVec::default(),
+            snapshot: 1,
+            ..Default::default()
+        },
+        ver: 2,
+    });
+    assert_eq!(Commit {safepoint: 1, discord: Permissive}, outcome);
+}
+
+#[test]
+fn discard_interned() {
+    let mut interner = Interner::new();
+    let mut examiner = Examiner::default();
+    let r1 = Record {
+        xid: Uuid::from_u128(1),
+        readset: vec!["x".to_string()],
+        writeset: vec!["y".to_string()],
+        readvers: SortedVec::default(),
+        snapshot: 0,
+        ..Default::default()
+    };
+    let r2 = Record {
+        xid: Uuid::from_u128(2),
+        readset: vec!["y".to_string()],
+        writeset: vec!["z".to_string()],
+        readvers: SortedVec::default(),
+        snapshot: 1,
+        ..Default::default()
+    };
+    let c1 = Candidate { rec: interner.intern_record(&r1), ver: 1 };
+    let c2 = Candidate { rec: interner.intern_record(&r2), ver: 2 };
+    assert_eq!(3, interner.len());
+    examiner.learn(c1.clone());
+    assert_eq!(Commit {safepoint: 1, discord: Permissive}, examiner.assess(c2.clone()));
+
+    // 'x' is only referenced by c1; 'y' is still referenced by c2
+    examiner.discard_interned(c1.truncated(), &mut interner);
+    assert_eq!(2, interner.len());
+    assert_eq!(None, interner.resolve(c1.rec.readset[0]));
+    assert_eq!(Some(&"y".to_string()), interner.resolve(c1.rec.writeset[0]));
+    assert!(!examiner.knows(&c1));
+    assert_knows(&examiner, &c2);
+
+    // an entry holding a released symbol is rejected before it is discarded
+    let mut released = c2.truncated();
+    released.readset.push(c1.rec.readset[0]);
+    let err = examiner.try_discard_interned(released, &mut interner).unwrap_err();
+    assert_eq!(ExaminerError::Interner(InternerError::NoSuchSymbol(c1.rec.readset[0])), err);
+    assert_eq!(Some(2), examiner.base());
+    assert_eq!(2, interner.len());
+
+    examiner.discard_interned(c2.truncated(), &mut interner);
+    assert!(interner.is_empty());
+    assert!(!examiner.knows(&c2));
+}
+
+#[test]
+fn scan_prefix() {
+    let scan = Scan::prefix("orders/2021-");
+    assert_eq!(Bound::Included("orders/2021-".to_string()), scan.start);
+    assert_eq!(Bound::Excluded("orders/2021.".to_string()), scan.end);
+    assert!(scan.contains(&"orders/2021-".into()));
+    assert!(scan.contains(&"orders/2021-10".into()));
+    assert!(!scan.contains(&"orders/2021".into()));
+    assert!(!scan.contains(&"orders/2022-01".into()));
+    assert!(!scan.is_empty());
+
+    // a trailing maximal character cannot be incremented, so the one before it is
+    let scan = Scan::prefix("a\u{10FFFF}");
+    assert_eq!(Bound::Excluded("b".to_string()), scan.end);
+    assert!(scan.contains(&"a\u{10FFFF}z".into()));
+
+    // the empty prefix spans all keys
+    let scan = Scan::prefix("");
+    assert_eq!(Bound::Unbounded, scan.end);
+    assert!(scan.contains(&"".into()));
+    assert!(scan.contains(&"zzz".into()));
+}
+
+#[test]
+fn scan_is_empty() {
+    assert!(Scan::new(Bound::Included(2), Bound::Included(1)).is_empty());
+    assert!(!Scan::new(Bound::Included(1), Bound::Included(1)).is_empty());
+    assert!(Scan::new(Bound::Included(1), Bound::Excluded(1)).is_empty());
+    assert!(Scan::new(Bound::Excluded(1), Bound::Excluded(1)).is_empty());
+    assert!(!Scan::new(Bound::Unbounded, Bound::Excluded(1)).is_empty());
+    assert!(!Scan::<u64>::new(Bound::Unbounded, Bound::Unbounded).is_empty());
+}
+
+#[test]
+fn assess_phantom() {
+    let mut examiner = Examiner::new();
+    examiner.learn(Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(1),
+            readset: vec![],
+            writeset: vec!["orders/2021-10".into()],
+            readvers: SortedVec::default(),
+            snapshot: 0,
+            ..Default::default()
+        },
+        ver: 1,
+    });
+    examiner.learn(Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(2),
+            readset: vec![],
+            writeset: vec!["orders/2022-01".into()],
+            readvers: SortedVec::default(),
+            snapshot: 0,
+            ..Default::default()
+        },
+        ver: 2,
+    });
+
+    // the insert at version 1 falls within the second scan
+    let candidate = Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(3),
+            readset: vec![],
+            scans: vec![Scan::prefix("orders/2020-"), Scan::prefix("orders/2021-")],
+            writeset: vec!["summary".into()],
+            readvers: SortedVec::default(),
+            snapshot: 0,
+            ..Default::default()
+        },
+        ver: 3,
+    };
+    let outcome = examiner.assess(candidate.clone());
+    assert_eq!(Abort {reason: Phantom {scan: 1, ver: 1}, discord: Assertive, refresh: None}, outcome);
+    assert_knows(&examiner, &candidate);
+
+    // having seen the insert, the scan does not conflict; the safepoint accounts for the write
+    let outcome = examiner.assess(Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(4),
+            readset: vec![],
+            scans: vec![Scan::prefix("orders/2021-")],
+            writeset: vec!["summary".into()],
+            readvers: SortedVec::default(),
+            snapshot: 1,
+            ..Default::default()
+        },
+        ver: 4,
+    });
+    assert_eq!(Commit {safepoint: 3, discord: Permissive}, outcome);
+}
+
+#[test]
+fn assess_write_into_scan() {
+    let mut examiner = Examiner::new();
+    examiner.learn(Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(1),
+            readset: vec![],
+            scans: vec![Scan::new(Bound::Included("b".into()), Bound::Excluded("d".into()))],
+            writeset: vec![],
+            readvers: SortedVec::default(),
+            snapshot: 0,
+            ..Default::default()
+        },
+        ver: 1,
+    });
+
+    // a write outside the scan does not depend on the scanner
+    let outcome = examiner.assess(Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(2),
+            readset: vec![],
+            writeset: vec!["d".into()],
+            readvers: SortedVec::default(),
+            snapshot: 0,
+            ..Default::default()
+        },
+        ver: 2,
+    });
+    assert_eq!(Commit {safepoint: 0, discord: Assertive}, outcome);
+
+    // a write inside the scan is ordered after the scanner
+    let outcome = examiner.assess(Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(3),
+            readset: vec![],
+            writeset: vec!["c".into()],
+            readvers: SortedVec::default(),
+            snapshot: 0,
+            ..Default::default()
+        },
+        ver: 3,
+    });
+    assert_eq!(Commit {safepoint: 1, discord: Assertive}, outcome);
+}
+
+#[test]
+fn discard_scans_and_ordered_writes() {
+    let mut examiner = Examiner::new();
+    let c1 = Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(1),
+            readset: vec![],
+            writeset: vec!["b".into()],
+            readvers: SortedVec::default(),
+            snapshot: 0,
+            ..Default::default()
+        },
+        ver: 1,
+    };
+    examiner.learn(c1.clone());
+    assert!(examiner.ordered_writes.is_none());
+
+    // the first scan builds the ordered index from the writes learned so far
+    let c2 = Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(2),
+            readset: vec![],
+            scans: vec![Scan::prefix("a")],
+            writeset: vec!["b".into(), "c".into()],
+            readvers: SortedVec::default(),
+            snapshot: 1,
+            ..Default::default()
+        },
+        ver: 2,
+    };
+    assert_eq!(Commit {safepoint: 1, discord: Permissive}, examiner.assess(c2.clone()));
+    assert_eq!(1, examiner.scans.len());
+    assert_eq!(vec![(&"b".to_string(), &2), (&"c".to_string(), &2)],
+               examiner.ordered_writes.as_ref().unwrap().iter().collect::<Vec<_>>());
+
+    examiner.discard(c1.truncated());
+    assert_eq!(1, examiner.scans.len());
+    assert_eq!(2, examiner.ordered_writes.as_ref().unwrap().len());
+
+    examiner.discard(c2.truncated());
+    assert!(examiner.scans.is_empty());
+    assert!(examiner.ordered_writes.as_ref().unwrap().is_empty());
+}
+
+fn items(num_items: usize) -> Vec<String> {
+    (0..num_items).map(|i| format!("item-{}", i)).collect()
+}
+
+// candidates over the given keys, with about the given share of their writes being commutative
+fn random_candidates(seed: u64, keys: &[String], commute_share: f64, num_candidates: usize) -> Vec<Candidate> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (1..=num_candidates as u64)
+        .map(|ver| {
+            let readset = (0..rng.gen_range(0..3))
+                .map(|_| keys[rng.gen_range(0..keys.len())].clone())
+                .collect();
+            let writes = (0..rng.gen_range(0..3))
+                .map(|_| keys[rng.gen_range(0..keys.len())].clone())
+                .collect::<Vec<_>>();
+            let snapshot = ver - 1 - rng.gen_range(0..std::cmp::min(ver, 4));
+            let (commutes, writeset) = writes
+                .into_iter()
+                .partition(|_| commute_share > 0.0 && rng.gen_bool(commute_share));
+            Candidate {
+                rec: Record {
+                    xid: Uuid::from_u128(ver as u128),
+                    readset,
+                    writeset,
+                    commutes,
+                    readvers: SortedVec::default(),
+                    snapshot,
+                    ..Default::default()
+                },
+                ver,
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn assess_batch_matches_sequential() {
+    for seed in 0..10 {
+        let candidates = random_candidates(seed, &items(8), 0.3, 200);
+        let mut sequential = Examiner::new();
+        let expected = candidates
+            .iter()
+            .map(|candidate| sequential.assess(candidate.clone()))
+            .collect::<Vec<_>>();
+        assert!(expected.iter().any(Outcome::is_abort));
+        assert!(expected.iter().any(Outcome::is_commit));
+
+        let mut batched = Examiner::new();
+        let outcomes = candidates
+            .chunks(16)
+            .flat_map(|chunk| batched.assess_batch(chunk))
+            .collect::<Vec<_>>();
+        assert_eq!(expected, outcomes, "seed {}", seed);
+        assert_eq!(sequential.digest(), batched.digest(), "seed {}", seed);
+        assert_eq!(sequential.metrics(), batched.metrics(), "seed {}", seed);
+    }
+
+    // a version 0 anywhere in the run fails it before any candidate is assessed
+    let mut candidates = random_candidates(0, &items(8), 0.0, 4);
+    candidates[3].ver = 0;
+    let mut examiner = Examiner::new();
+    assert_eq!(Err(ExaminerError::UnsupportedVersion), examiner.try_assess_batch(&candidates));
+    assert_eq!(0, examiner.metrics().outcomes.commits() + examiner.metrics().outcomes.aborts());
+}
+
+#[test]
+fn assess_detailed_antidependency() {
+    let mut examiner = Examiner::new();
+    for (ver, writeset) in [(1, vec!["x", "y"]), (2, vec!["y"]), (3, vec!["z"])] {
+        examiner.learn(Candidate {
+            rec: Record {
+                xid: Uuid::from_u128(ver as u128),
+                readset: vec![],
+                writeset: writeset.into_iter().map(String::from).collect(),
+                readvers: SortedVec::default(),
+                snapshot: 0,
+                ..Default::default()
+            },
+            ver,
+        });
+    }
+    examiner.learn(Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(4),
+            readset: vec![],
+            writeset: vec!["w/1".into()],
+            readvers: SortedVec::default(),
+            snapshot: 0,
+            ..Default::default()
+        },
+        ver: 4,
+    });
+
+    // 'x' was written at 1 (within the snapshot), 'z' at 3 (a known readver); only 'y' and
+    // the scanned 'w/1' conflict
+    let candidate = Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(5),
+            readset: vec!["x".into(), "y".into(), "z".into()],
+            scans: vec![Scan::prefix("w/")],
+            writeset: vec![],
+            readvers: vec![3].into(),
+            snapshot: 1,
+            ..Default::default()
+        },
+        ver: 5,
+    };
+    let (outcome, detail) = examiner.assess_detailed(candidate.clone());
+    assert_eq!(Abort {reason: Antidependency(2), discord: Assertive, refresh: None}, outcome);
+    assert_eq!(Some(AbortDetail::Antidependency {
+        conflicts: vec![("y".into(), 2), ("w/1".into(), 4)]
+    }), detail);
+    assert_knows(&examiner, &candidate);
+}
+
+#[test]
+fn assess_detailed_staleness() {
+    let mut examiner = Examiner::new();
+    examiner.base = 12;
+    let (outcome, detail) = examiner.assess_detailed(Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(1),
+            readset: vec!["v".into()],
+            writeset: vec![],
+            readvers: SortedVec::default(),
+            snapshot: 10,
+            ..Default::default()
+        },
+        ver: 15,
+    });
+    assert_eq!(Abort {reason: Staleness, discord: Permissive, refresh: None}, outcome);
+    assert_eq!(Some(AbortDetail::Staleness {snapshot: 10, base: 12}), detail);
+}
+
+#[test]
+fn assess_detailed_commit() {
+    let mut examiner = Examiner::new();
+    let (outcome, detail) = examiner.assess_detailed(Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(1),
+            readset: vec!["x".into()],
+            writeset: vec!["x".into()],
+            readvers: SortedVec::default(),
+            snapshot: 0,
+            ..Default::default()
+        },
+        ver: 1,
+    });
+    assert_eq!(Commit {safepoint: 0, discord: Permissive}, outcome);
+    assert_eq!(None, detail);
+}
+
+// every way of assessing a candidate agrees with assess(), and with what peek() and query()
+// foresee, across isolation levels, hierarchical keys, commutative writes and scans
+#[test]
+fn assessments_agree() {
+    for isolation in [Serializable, Snapshot] {
+        for hierarchy in [false, true] {
+            for seed in 0..10 {
+                let keys = match hierarchy {
+                    false => items(6),
+                    true => hierarchical_items(),
+                };
+                let mut candidates = random_candidates(seed, &keys, 0.3, 200);
+                // repeated writes within a candidate meet themselves
+                candidates[0].rec.writeset = vec![keys[0].clone(), keys[0].clone()];
+                for candidate in candidates.iter_mut().step_by(7) {
+                    candidate.rec.scans.push(Scan::prefix(&keys[1]));
+                }
+
+                let examiner = || {
+                    let examiner = Examiner::new().with_isolation(isolation).with_refresh_hints();
+                    match hierarchy {
+                        false => examiner,
+                        true => examiner.with_hierarchy(),
+                    }
+                };
+                let (mut plain, mut detailed, mut explained, mut keyed) = (examiner(), examiner(), examiner(), examiner());
+                for (index, candidate) in candidates.into_iter().enumerate() {
+                    // learning a candidate, whether borrowed or not, leaves the examiners in step
+                    if index % 5 == 4 {
+                        for examiner in [&mut detailed, &mut explained, &mut keyed] {
+                            examiner.learn_record(&candidate.rec, candidate.ver);
+                        }
+                        plain.learn(candidate);
+                        continue;
+                    }
+
+                    let peeked = plain.peek(&candidate);
+                    assert_eq!(peeked.is_commit(), plain.would_commit(&candidate), "seed {}", seed);
+                    let mut read_only = candidate.clone();
+                    read_only.rec.writeset.clear();
+                    read_only.rec.commutes.clear();
+                    assert_eq!(plain.peek(&read_only), plain.query(&read_only.rec), "seed {}", seed);
+
+                    let (detailed_outcome, detail) = detailed.assess_detailed(candidate.clone());
+                    let (explained_outcome, explanation) = explained.assess_explained(candidate.clone());
+                    let (keyed_outcome, safepoints) = keyed.assess_keyed(&candidate.rec, candidate.ver);
+                    let outcome = plain.assess(candidate.clone());
+                    assert_eq!(peeked, outcome, "seed {}", seed);
+                    for other in [detailed_outcome, explained_outcome, keyed_outcome] {
+                        assert_eq!(outcome, other, "seed {}", seed);
+                    }
+
+                    // the detail, the rule and the refresh hint bear out the outcome
+                    match (&outcome, &detail, explanation.rule) {
+                        (Commit {discord: Assertive, ..}, None, Rule::R1) | (Commit {discord: Permissive, ..}, None, Rule::R4) => {}
+                        (Abort {reason: Staleness, ..}, Some(AbortDetail::Staleness {..}), Rule::R2) => {}
+                        (Abort {reason: Antidependency(ver), ..}, Some(AbortDetail::Antidependency {conflicts}), Rule::R3)
+                        | (Abort {reason: Phantom {ver, ..}, ..}, Some(AbortDetail::Antidependency {conflicts}), Rule::R3)
+                        | (Abort {reason: WriteConflict(ver), ..}, Some(AbortDetail::WriteConflict {conflicts}), Rule::R3) => {
+                            assert_eq!(*ver, conflicts[0].1, "seed {}", seed);
+                        }
+                        other => panic!("unexpected {:?} (seed {})", other, seed),
+                    }
+                    let refresh = match &outcome {
+                        Commit {..} => None,
+                        Abort {refresh, ..} => refresh.as_ref(),
+                    };
+                    match (detail, refresh) {
+                        (None, None) => {}
+                        (Some(detail), Some(refresh)) => {
+                            assert!(refresh.snapshot > candidate.rec.snapshot && refresh.snapshot < candidate.ver, "seed {}", seed);
+                            assert_eq!(&Refresh::new(&candidate.rec, &detail), refresh, "seed {}", seed);
+                        }
+                        other => panic!("unexpected {:?} (seed {})", other, seed),
+                    }
+
+                    // as do the intersections and the safepoint of each write
+                    match outcome {
+                        Commit {safepoint, ..} => {
+                            let num_writes = candidate.rec.writeset.len() + candidate.rec.commutes.len();
+                            assert_eq!(num_writes, safepoints.len(), "seed {}", seed);
+                            assert!(safepoints.iter().all(|&key_safepoint| key_safepoint <= safepoint), "seed {}", seed);
+                            assert!(safepoints.is_empty() || safepoints.contains(&safepoint), "seed {}", seed);
+                            assert!(explanation.intersections.iter().all(|&(_, _, ver)| ver <= safepoint), "seed {}", seed);
+                        }
+                        Abort {..} => assert!(safepoints.is_empty() && explanation.intersections.is_empty()),
+                    }
+                }
+                for other in [&detailed, &explained, &keyed] {
+                    assert_eq!(plain.reads, other.reads, "seed {}", seed);
+                    assert_eq!(plain.writes, other.writes, "seed {}", seed);
+                    assert_eq!(plain.commutes, other.commutes, "seed {}", seed);
+                    assert_eq!(plain.digest(), other.digest(), "seed {}", seed);
+                }
+                assert!(plain.metrics().commutes > 0 && !plain.scans.is_empty(), "seed {}", seed);
+            }
+        }
+    }
+}
+
+#[test]
+fn assess_explained_scans() {
+    let mut examiner = Examiner::new();
+    examiner.learn(Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(1),
+            readset: vec![],
+            scans: vec![Scan::prefix("a/")],
+            writeset: vec!["b/1".into()],
+            readvers: SortedVec::default(),
+            snapshot: 0,
+            ..Default::default()
+        },
+        ver: 1,
+    });
+    let (outcome, explanation) = examiner.assess_explained(Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(2),
+            readset: vec![],
+            scans: vec![Scan::prefix("b/")],
+            writeset: vec!["a/1".into()],
+            readvers: SortedVec::default(),
+            snapshot: 1,
+            ..Default::default()
+        },
+        ver: 2,
+    });
+    assert_eq!(Commit {safepoint: 1, discord: Permissive}, outcome);
+    assert_eq!(Explanation {
+        rule: Rule::R4,
+        intersections: vec![(WriteScan, "b/1".into(), 1), (ScanWrite, "a/1".into(), 1)]
+    }, explanation);
+
+    let lengths = explanation.map(|key| key.len());
+    assert_eq!(vec![(WriteScan, 3, 1), (ScanWrite, 3, 1)], lengths.intersections);
+}
+
+#[test]
+fn peek_leaves_examiner_unchanged() {
+    let mut examiner = Examiner::new();
+    let candidate = Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(1),
+            readset: vec!["x".into()],
+            writeset: vec!["y".into()],
+            readvers: SortedVec::default(),
+            snapshot: 4,
+            ..Default::default()
+        },
+        ver: 5,
+    };
+    assert_eq!(Commit {safepoint: 4, discord: Permissive}, examiner.peek(&candidate));
+    assert_eq!(None, examiner.base());
+    assert!(!examiner.knows(&candidate));
+
+    examiner.learn(candidate.clone());
+    let stale = Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(2),
+            readset: vec!["y".into()],
+            writeset: vec![],
+            readvers: SortedVec::default(),
+            snapshot: 3,
+            ..Default::default()
+        },
+        ver: 6,
+    };
+    assert_eq!(Abort {reason: Staleness, discord: Permissive, refresh: None}, examiner.peek(&stale));
+    assert!(!examiner.would_commit(&stale));
+    assert!(!examiner.knows(&stale));
+}
+
+#[test]
+fn peek_scans() {
+    let mut examiner = Examiner::new();
+    for (ver, write) in [(1, "a/2"), (2, "a/1"), (3, "b/1")] {
+        examiner.learn(Candidate {
+            rec: Record {
+                xid: Uuid::from_u128(ver as u128),
+                readset: vec![],
+                writeset: vec![write.into()],
+                readvers: SortedVec::default(),
+                snapshot: 0,
+                ..Default::default()
+            },
+            ver,
+        });
+    }
+    let candidate = Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(4),
+            readset: vec![],
+            scans: vec![Scan::prefix("b/"), Scan::prefix("a/")],
+            writeset: vec![],
+            readvers: vec![3].into(),
+            snapshot: 0,
+            ..Default::default()
+        },
+        ver: 4,
+    };
+
+    // without an ordered index, the first phantom is still found in key order
+    assert!(examiner.ordered_writes.is_none());
+    let peeked = examiner.peek(&candidate);
+    assert_eq!(Abort {reason: Phantom {scan: 1, ver: 2}, discord: Assertive, refresh: None}, peeked);
+    assert_eq!(peeked, examiner.assess(candidate.clone()));
+
+    // ...and likewise with one
+    assert!(examiner.ordered_writes.is_some());
+    let candidate = Candidate { ver: 5, ..candidate };
+    assert_eq!(examiner.peek(&candidate), examiner.assess(candidate));
+}
+
+// two candidates on the same snapshot, each reading both items and writing one of them
+fn write_skew() -> (Candidate, Candidate) {
+    let candidate = |ver: u64, write: &str| Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(ver as u128),
+            readset: vec!["x".into(), "y".into()],
+            writeset: vec![write.into()],
+            readvers: SortedVec::default(),
+            snapshot: 0,
+            ..Default::default()
+        },
+        ver,
+    };
+    (candidate(1, "x"), candidate(2, "y"))
+}
+
+#[test]
+fn write_skew_serializable() {
+    let mut examiner = Examiner::new();
+    assert_eq!(Serializable, examiner.isolation());
+    let (c1, c2) = write_skew();
+    assert_eq!(Commit {safepoint: 0, discord: Permissive}, examiner.assess(c1));
+    assert_eq!(Abort {reason: Antidependency(1), discord: Assertive, refresh: None}, examiner.assess(c2));
+}
+
+#[test]
+fn write_skew_snapshot() {
+    let mut examiner = Examiner::new().with_isolation(Snapshot);
+    assert_eq!(Snapshot, examiner.isolation());
+    let (c1, c2) = write_skew();
+    assert_eq!(Commit {safepoint: 0, discord: Permissive}, examiner.assess(c1));
+    // the stale read of x is admitted, but must be installed after the write to x
+    let (outcome, explanation) = examiner.assess_explained(c2);
+    assert_eq!(Commit {safepoint: 1, discord: Permissive}, outcome);
+    assert_eq!(Explanation {
+        rule: Rule::R4,
+        intersections: vec![(WriteRead, "x".into(), 1), (ReadWrite, "y".into(), 1)],
+    }, explanation);
+}
+
+#[test]
+fn lost_update_snapshot() {
+    let mut examiner = Examiner::new().with_isolation(Snapshot);
+    let candidate = |ver: u64, readvers: Vec<u64>| Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(ver as u128),
+            readset: vec!["x".into()],
+            writeset: vec!["x".into()],
+            readvers: readvers.into(),
+            snapshot: 0,
+            ..Default::default()
+        },
+        ver,
+    };
+    assert_eq!(Commit {safepoint: 0, discord: Permissive}, examiner.assess(candidate(1, vec![])));
+
+    // overwriting x without having read its latest version is a lost update
+    let (outcome, detail) = examiner.assess_detailed(candidate(2, vec![]));
+    assert_eq!(Abort {reason: WriteConflict(1), discord: Assertive, refresh: None}, outcome);
+    assert_eq!(Some(AbortDetail::WriteConflict {
+        conflicts: vec![("x".into(), 1)],
+    }), detail);
+
+    // ...unless that version was read
+    let outcome = examiner.assess(candidate(3, vec![2]));
+    assert_eq!(Commit {safepoint: 2, discord: Permissive}, outcome);
+}
+
+#[test]
+fn scans_snapshot() {
+    let mut examiner = Examiner::new().with_isolation(Snapshot);
+    let write = Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(1),
+            readset: vec![],
+            writeset: vec!["a/1".into()],
+            readvers: SortedVec::default(),
+            snapshot: 0,
+            ..Default::default()
+        },
+        ver: 1,
+    };
+    assert!(examiner.assess(write).is_commit());
+
+    // phantoms are admitted under snapshot isolation
+    let scan = Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(2),
+            readset: vec![],
+            scans: vec![Scan::prefix("a/")],
+            writeset: vec!["b/1".into()],
+            readvers: SortedVec::default(),
+            snapshot: 0,
+            ..Default::default()
+        },
+        ver: 2,
+    };
+    assert_eq!(Commit {safepoint: 1, discord: Permissive}, examiner.peek(&scan));
+    assert_eq!(Commit {safepoint: 1, discord: Permissive}, examiner.assess(scan));
+}
+
+// assesses the candidates, discarding each once it falls a fixed distance behind, as a suffix would
+fn assess_with_discards(examiner: &mut Examiner, candidates: &[Candidate], from: usize) -> Vec<Outcome> {
+    const EXTENT: usize = 16;
+    let mut outcomes = vec![];
+    for index in from..candidates.len() {
+        outcomes.push(examiner.assess(candidates[index].clone()));
+        if index >= EXTENT {
+            examiner.discard(candidates[index - EXTENT].truncated());
+        }
+    }
+    outcomes
+}
+
+#[test]
+fn checkpoint_restore_mid_stream() {
+    for isolation in [Serializable, Snapshot] {
+        for seed in 0..5 {
+            let candidates = random_candidates(seed, &items(8), 0.0, 120);
+            let expected = assess_with_discards(&mut Examiner::new().with_isolation(isolation), &candidates, 0);
+            for split in (0..candidates.len()).step_by(13) {
+                let mut examiner = Examiner::new().with_isolation(isolation);
+                let mut outcomes = assess_with_discards(&mut examiner, &candidates[..split], 0);
+                let checkpoint = examiner.checkpoint();
+                drop(examiner);
+
+                let mut restored: Examiner = Examiner::restore(&checkpoint).unwrap();
+                assert_eq!(isolation, restored.isolation());
+                outcomes.extend(assess_with_discards(&mut restored, &candidates, split));
+                assert_eq!(expected, outcomes, "seed {}, split {}", seed, split);
+            }
+        }
+    }
+}
+
+#[test]
+fn checkpoint_restore_scans() {
+    let mut examiner = Examiner::new();
+    let candidate = |ver: u64, scans: Vec<Scan<String>>, writeset: Vec<String>| Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(ver as u128),
+            readset: vec![],
+            scans,
+            writeset,
+            readvers: SortedVec::default(),
+            snapshot: ver - 1,
+            ..Default::default()
+        },
+        ver,
+    };
+    let scans = vec![
+        Scan::prefix("a/"),
+        Scan::new(Bound::Excluded("c".into()), Bound::Unbounded),
+    ];
+    assert!(examiner.assess(candidate(1, scans, vec![])).is_commit());
+    assert!(examiner.assess(candidate(2, vec![], vec!["b".into()])).is_commit());
+
+    let mut restored: Examiner = Examiner::restore(&examiner.checkpoint()).unwrap();
+    assert_eq!(examiner.base(), restored.base());
+    for (ver, scans, writeset) in [
+        (3, vec![], vec!["a/1".into()]),
+        (4, vec![], vec!["d".into()]),
+        (5, vec![Scan::prefix("a/")], vec![]),
+    ] {
+        let outcome = examiner.assess(candidate(ver, scans.clone(), writeset.clone()));
+        assert_eq!(outcome, restored.assess(candidate(ver, scans, writeset)));
+    }
+}
+
+#[test]
+fn restore_malformed() {
+    let examiner = Examiner::new();
+    let checkpoint = examiner.checkpoint();
+    assert_eq!(Some(CheckpointError::Truncated), Examiner::<String>::restore(&checkpoint[..checkpoint.len() - 1]).err());
+    let mut bad_isolation = checkpoint.clone();
+    bad_isolation[6] = 9;
+    assert_eq!(Some(CheckpointError::InvalidTag(9)), Examiner::<String>::restore(&bad_isolation).err());
+    let suffix_checkpoint = crate::suffix::Suffix::default().checkpoint();
+    assert_eq!(Some(CheckpointError::BadMagic), Examiner::<String>::restore(&suffix_checkpoint).err());
+}
+
+// drives an examiner and a suffix as a certifier would, spacing candidates with decisions so that
+// the suffix has gaps and completing each candidate a few offsets after it is assessed
+struct Certifier {
+    examiner: Examiner,
+    suffix: Suffix,
+}
+
+impl Certifier {
+    const DECISION_LAG: u64 = 3;
+
+    fn certify(&mut self, candidate: Candidate) -> Outcome {
+        let ver = candidate.ver;
+        self.suffix.append_record(candidate.rec.clone(), ver);
+        let outcome = self.examiner.assess(candidate);
+        self.suffix.set_learning(ver, self.examiner.learning(ver));
+        let decided = ver.saturating_sub(2 * Self::DECISION_LAG);
+        if self.suffix.get(decided).is_some() {
+            self.suffix.complete(decided);
+        }
+        if let Some(truncated) = self.suffix.truncate(4, 8) {
+            for entry in truncated {
+                self.examiner.discard(entry);
+            }
+        }
+        outcome
+    }
+}
+
+#[test]
+fn rebuild_from_suffix() {
+    for (seed, precise) in (0..5).flat_map(|seed| [(seed, false), (seed, true)]) {
+        // candidates are placed at odd offsets, decisions at even ones; some scan, and some of
+        // their writes commute
+        let candidates = random_candidates(seed, &items(8), 0.3, 150)
+            .into_iter()
+            .map(|mut candidate| {
+                if candidate.ver % 7 == 0 {
+                    candidate.rec.scans.push(Scan::prefix("item-1"));
+                }
+                candidate.ver = 2 * candidate.ver - 1;
+                candidate.rec.snapshot = candidate.rec.snapshot.saturating_mul(2).saturating_sub(1);
+                candidate
+            })
+            .collect::<Vec<_>>();
+        let examiner = |examiner: Examiner| if precise { examiner.with_precise_aborts() } else { examiner };
+        let mut full = Certifier { examiner: examiner(Examiner::new()), suffix: Suffix::default() };
+        let mut outcomes = vec![];
+        for (index, candidate) in candidates.iter().enumerate() {
+            if index % 25 == 24 {
+                // the rebuilt examiner holds what the full-stream one does, and so agrees with it
+                // on every remaining outcome
+                let mut rebuilt = Certifier {
+                    examiner: examiner(Examiner::rebuild(&full.suffix)),
+                    suffix: Suffix::restore(&full.suffix.checkpoint()).unwrap(),
+                };
+                let mut replica = Certifier {
+                    examiner: Examiner::restore(&full.examiner.checkpoint()).unwrap(),
+                    suffix: Suffix::restore(&full.suffix.checkpoint()).unwrap(),
+                };
+                for candidate in &candidates[index..] {
+                    let context = format!("seed {}, precise {}, ver {}", seed, precise, candidate.ver);
+                    assert_eq!(replica.examiner.base, rebuilt.examiner.base, "{}", context);
+                    assert_eq!(replica.examiner.reads, rebuilt.examiner.reads, "{}", context);
+                    assert_eq!(replica.examiner.writes, rebuilt.examiner.writes, "{}", context);
+                    assert_eq!(replica.examiner.commutes, rebuilt.examiner.commutes, "{}", context);
+                    assert_eq!(replica.examiner.scans, rebuilt.examiner.scans, "{}", context);
+                    assert_eq!(replica.examiner.uncertain, rebuilt.examiner.uncertain, "{}", context);
+                    assert_eq!(replica.examiner.unlearned, rebuilt.examiner.unlearned, "{}", context);
+                    assert_eq!(replica.certify(candidate.clone()), rebuilt.certify(candidate.clone()), "{}", context);
+                }
+                assert!(rebuilt.examiner.validate(&rebuilt.suffix).is_empty());
+            }
+            outcomes.push(full.certify(candidate.clone()));
+        }
+        assert!(outcomes.iter().any(Outcome::is_commit));
+        assert!(outcomes.iter().any(|outcome| matches!(outcome, Abort { reason: Phantom { .. }, .. })));
+    }
+}
+
+#[test]
+fn rebuild_from_empty_suffix() {
+    let examiner: Examiner = Examiner::rebuild(&Suffix::default());
+    assert_eq!(None, examiner.base());
+    assert!(examiner.reads.is_empty() && examiner.writes.is_empty());
+}
+
+#[test]
+fn rebuild_precise() {
+    // the entry that aborted assertively was left unlearned, and is rebuilt as such
+    let mut certifier = Certifier { examiner: Examiner::new().with_precise_aborts(), suffix: Suffix::default() };
+    assert!(certifier.certify(counter(1, vec![], vec!["x".into()], vec![], 0)).is_commit());
+    assert!(certifier.certify(counter(2, vec!["x".into()], vec!["y".into()], vec![], 0)).is_abort());
+    assert_eq!(Learning::Unlearned, certifier.suffix.get(2).unwrap().learning);
+    let examiner: Examiner = Examiner::rebuild(&certifier.suffix).with_precise_aborts();
+    assert_eq!(None, examiner.writes.get(&"y".into()));
+    assert_eq!(Learning::Unlearned, examiner.learning(2));
+    assert!(examiner.validate(&certifier.suffix).is_empty());
+}
+
+#[test]
+fn metrics() {
+    let mut examiner = Examiner::new();
+    assert_eq!(ExaminerMetrics {
+        base: None,
+        reads: 0,
+        writes: 0,
+        scans: 0,
+        outcomes: OutcomeCounts::default(),
+        discards: 0,
+        forced_advances: 0,
+        intents: 0,
+        commutes: 0,
+    }, examiner.metrics());
+
+    let candidate = |ver: u64, readset: Vec<String>, scans: Vec<Scan<String>>, writeset: Vec<String>, snapshot: u64| Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(ver as u128),
+            readset,
+            scans,
+            writeset,
+            readvers: SortedVec::default(),
+            snapshot,
+            ..Default::default()
+        },
+        ver,
+    };
+    let c1 = candidate(1, vec![], vec![], vec!["x".into()], 0);
+    assert!(examiner.assess(c1.clone()).is_commit());
+    assert!(examiner.assess(candidate(2, vec!["x".into(), "y".into()], vec![], vec!["y".into()], 1)).is_commit());
+    assert!(examiner.assess(candidate(3, vec!["y".into()], vec![], vec![], 1)).is_abort());
+    assert!(examiner.assess(candidate(4, vec![], vec![Scan::prefix("")], vec![], 1)).is_abort());
+    assert!(examiner.peek(&candidate(5, vec![], vec![], vec![], 0)).is_commit());
+    examiner.discard(c1.truncated());
+    assert!(examiner.assess(candidate(5, vec!["z".into()], vec![], vec![], 0)).is_abort());
+
+    let metrics = examiner.metrics();
+    assert_eq!(ExaminerMetrics {
+        base: Some(2),
+        reads: 3,
+        writes: 1,
+        scans: 1,
+        outcomes: OutcomeCounts {
+            r1_commits: 1,
+            r2_staleness_aborts: DiscordCounts { assertive: 0, permissive: 1 },
+            r3_antidependency_aborts: DiscordCounts { assertive: 1, permissive: 0 },
+            r3_phantom_aborts: DiscordCounts { assertive: 1, permissive: 0 },
+            r3_write_conflict_aborts: DiscordCounts::default(),
+            r4_commits: 1,
+        },
+        discards: 1,
+        forced_advances: 0,
+        intents: 0,
+        commutes: 0,
+    }, metrics);
+    assert_eq!(2, metrics.outcomes.commits());
+    assert_eq!(3, metrics.outcomes.aborts());
+}
+
+#[test]
+fn max_keys_forces_base() {
+    let mut examiner = Examiner::new().with_max_keys(8);
+    let candidate = |ver: u64, readset: Vec<String>, writeset: Vec<String>, snapshot: u64| Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(ver as u128),
+            readset,
+            writeset,
+            readvers: SortedVec::default(),
+            snapshot,
+            ..Default::default()
+        },
+        ver,
+    };
+    let entries = (1..=4)
+        .map(|ver| candidate(ver, vec![format!("r{}", ver)], vec![format!("w{}", ver)], ver - 1))
+        .collect::<Vec<_>>();
+    for entry in &entries {
+        assert!(examiner.assess(entry.clone()).is_commit());
+    }
+    assert_eq!(8, examiner.tracked_keys());
+    assert_eq!(Some(1), examiner.base());
+
+    // the ninth key evicts down to six, forgetting versions 1 and 2
+    let c5 = candidate(5, vec![], vec!["w5".into()], 4);
+    assert_eq!(Commit {safepoint: 0, discord: Assertive}, examiner.assess(c5));
+    assert_eq!(5, examiner.tracked_keys());
+    assert_eq!(Some(3), examiner.base());
+    assert_eq!(1, examiner.metrics().forced_advances);
+
+    // a candidate that may have missed a forgotten write is stale; one that saw it is not
+    let stale = candidate(6, vec!["w1".into()], vec![], 1);
+    assert_eq!(Abort {reason: Staleness, discord: Permissive, refresh: None}, examiner.assess(stale));
+    let fresh = candidate(7, vec!["w1".into()], vec!["w2".into()], 2);
+    assert_eq!(Commit {safepoint: 2, discord: Permissive}, examiner.assess(fresh));
+
+    // the suffix later discards the forgotten entries as no-ops, and the rest as usual
+    for entry in &entries {
+        examiner.discard(entry.truncated());
+    }
+    assert_eq!(Some(5), examiner.base());
+    assert!(examiner.tracked_keys() <= 8);
+}
+
+#[test]
+fn max_keys_bounds_tables() {
+    for seed in 0..5 {
+        let candidates = random_candidates(seed, &items(64), 0.0, 300);
+        let mut uncapped = Examiner::new();
+        let mut capped = Examiner::new().with_max_keys(6);
+        for candidate in candidates {
+            let (base, capped_base) = (uncapped.base, capped.base);
+            let expected = uncapped.assess(candidate.clone());
+            let snapshot = candidate.rec.snapshot;
+            let outcome = capped.assess(candidate);
+            assert!(capped.tracked_keys() <= 6);
+
+            // capping only ever makes the examiner stricter
+            match (&expected, &outcome) {
+                (expected, outcome) if expected == outcome => {}
+                (_, Abort {reason: Staleness, discord: Permissive, refresh: None}) => {
+                    assert!(snapshot >= base.saturating_sub(1));
+                }
+                (Commit {safepoint: expected, ..}, Commit {safepoint, ..}) => {
+                    assert!(safepoint > expected);
+                    assert!(*safepoint >= capped_base - 1);
+                }
+                _ => panic!("expected {:?}, got {:?} (seed {})", expected, outcome, seed),
+            }
+        }
+        assert!(capped.metrics().forced_advances > 0);
+        assert!(capped.metrics().outcomes.r2_staleness_aborts.total() > uncapped.metrics().outcomes.r2_staleness_aborts.total());
+    }
+}
+
+#[test]
+fn max_keys_checkpoint() {
+    let mut examiner = Examiner::new().with_max_keys(4);
+    for candidate in random_candidates(0, &items(16), 0.0, 20) {
+        examiner.assess(candidate);
+    }
+    let restored: Examiner = Examiner::restore(&examiner.checkpoint()).unwrap();
+    assert_eq!(Some(4), restored.max_keys);
+    assert_eq!(examiner.forced_base, restored.forced_base);
+}
+
+fn hierarchical_items() -> Vec<String> {
+    ["t", "t/0", "t/1", "t/1/a", "u", "u/0"].iter().map(|&item| String::from(item)).collect()
+}
+
+#[test]
+fn hierarchy_coarse_read_fine_write() {
+    let mut examiner = Examiner::new().with_hierarchy();
+    let table_reader = Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(1),
+            readset: vec!["t".into()],
+            writeset: vec!["u".into()],
+            readvers: SortedVec::default(),
+            snapshot: 0,
+            ..Default::default()
+        },
+        ver: 1,
+    };
+    let row_writer = Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(2),
+            readset: vec![],
+            writeset: vec!["t/1/a".into()],
+            readvers: SortedVec::default(),
+            snapshot: 0,
+            ..Default::default()
+        },
+        ver: 2,
+    };
+    assert_eq!(Commit {safepoint: 0, discord: Permissive}, examiner.assess(table_reader));
+    // the row write meets the table read
+    assert_eq!(Commit {safepoint: 1, discord: Assertive}, examiner.assess(row_writer));
+
+    // a table read that missed the row write is antidependent
+    let candidate = Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(3),
+            readset: vec!["t".into()],
+            writeset: vec![],
+            readvers: SortedVec::default(),
+            snapshot: 1,
+            ..Default::default()
+        },
+        ver: 3,
+    };
+    let (outcome, detail) = examiner.assess_detailed(candidate);
+    assert_eq!(Abort {reason: Antidependency(2), discord: Assertive, refresh: None}, outcome);
+    assert_eq!(Some(AbortDetail::Antidependency {conflicts: vec![("t".into(), 2)]}), detail);
+}
+
+#[test]
+fn hierarchy_fine_read_coarse_write() {
+    let mut examiner = Examiner::new().with_hierarchy();
+    examiner.learn(Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(1),
+            readset: vec!["t/0".into()],
+            writeset: vec![],
+            readvers: SortedVec::default(),
+            snapshot: 0,
+            ..Default::default()
+        },
+        ver: 1,
+    });
+    let table_writer = Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(2),
+            readset: vec![],
+            writeset: vec!["t".into()],
+            readvers: SortedVec::default(),
+            snapshot: 0,
+            ..Default::default()
+        },
+        ver: 2,
+    };
+    let (outcome, explanation) = examiner.assess_explained(table_writer);
+    assert_eq!(Commit {safepoint: 1, discord: Assertive}, outcome);
+    assert_eq!(vec![(ReadWrite, "t".into(), 1)], explanation.intersections);
+
+    // a row read that missed the table write is antidependent, unless the write is known
+    let row_reader = |ver: u64, readvers: Vec<u64>| Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(ver as u128),
+            readset: vec!["t/1/a".into()],
+            writeset: vec![],
+            readvers: readvers.into(),
+            snapshot: 1,
+            ..Default::default()
+        },
+        ver,
+    };
+    assert_eq!(Abort {reason: Antidependency(2), discord: Assertive, refresh: None}, examiner.peek(&row_reader(3, vec![])));
+    assert_eq!(Commit {safepoint: 2, discord: Permissive}, examiner.assess(row_reader(3, vec![2])));
+
+    // unrelated keys and siblings do not meet
+    let sibling = Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(4),
+            readset: vec!["t/0".into(), "tt".into()],
+            writeset: vec!["t/1".into()],
+            readvers: SortedVec::default(),
+            snapshot: 3,
+            ..Default::default()
+        },
+        ver: 4,
+    };
+    assert_eq!(Commit {safepoint: 3, discord: Permissive}, examiner.assess(sibling));
+}
+
+#[test]
+fn hierarchy_write_conflict_snapshot() {
+    let mut examiner = Examiner::new().with_isolation(Snapshot).with_hierarchy();
+    let writer = |ver: u64, write: &str| Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(ver as u128),
+            readset: vec!["v".into()],
+            writeset: vec![write.into()],
+            readvers: SortedVec::default(),
+            snapshot: 1,
+            ..Default::default()
+        },
+        ver,
+    };
+    assert!(examiner.assess(writer(2, "t/0")).is_commit());
+    assert_eq!(Abort {reason: WriteConflict(2), discord: Assertive, refresh: None}, examiner.assess(writer(3, "t")));
+    assert_eq!(Abort {reason: WriteConflict(3), discord: Assertive, refresh: None}, examiner.assess(writer(4, "t/1")));
+}
+
+#[test]
+fn hierarchy_disabled() {
+    let mut examiner = Examiner::new();
+    for candidate in random_candidates(0, &hierarchical_items(), 0.0, 50) {
+        examiner.assess(candidate);
+    }
+    assert!(examiner.read_intents.is_empty() && examiner.write_intents.is_empty());
+    assert_eq!(0, examiner.metrics().intents);
+}
+
+#[test]
+fn hierarchy_stricter_than_flat() {
+    for isolation in [Serializable, Snapshot] {
+        for seed in 0..10 {
+            let mut examiner = Examiner::new().with_isolation(isolation).with_hierarchy();
+            let mut flat = Examiner::new().with_isolation(isolation);
+            let mut stricter = 0;
+            for candidate in random_candidates(seed, &hierarchical_items(), 0.0, 200) {
+                let outcome = examiner.assess(candidate.clone());
+                if flat.assess(candidate).is_commit() && outcome.is_abort() {
+                    stricter += 1;
+                }
+            }
+            assert!(stricter > 0, "seed {}", seed);
+        }
+    }
+}
+
+#[test]
+fn hierarchy_discard_clears_intents() {
+    let candidates = random_candidates(0, &hierarchical_items(), 0.0, 100);
+    let mut examiner = Examiner::new().with_hierarchy();
+    for candidate in &candidates {
+        examiner.assess(candidate.clone());
+    }
+    assert!(examiner.metrics().intents > 0);
+    for candidate in &candidates {
+        examiner.discard(candidate.truncated());
+    }
+    assert!(examiner.reads.is_empty() && examiner.writes.is_empty());
+    assert!(examiner.read_intents.is_empty() && examiner.write_intents.is_empty());
+}
+
+#[test]
+fn hierarchy_restored() {
+    let candidates = random_candidates(1, &hierarchical_items(), 0.0, 200);
+    let mut examiner = Examiner::new().with_hierarchy();
+    assess_with_discards(&mut examiner, &candidates[..100], 0);
+    let mut restored: Examiner = Examiner::restore(&examiner.checkpoint()).unwrap().with_hierarchy();
+    assert_eq!(examiner.read_intents, restored.read_intents);
+    assert_eq!(examiner.write_intents, restored.write_intents);
+    assert_eq!(
+        assess_with_discards(&mut examiner, &candidates, 100),
+        assess_with_discards(&mut restored, &candidates, 100)
+    );
+}
+
+fn counter(ver: u64, readset: Vec<String>, writeset: Vec<String>, commutes: Vec<String>, snapshot: u64) -> Candidate {
+    Candidate {
+        rec: Record {
+            xid: Uuid::from_u128(ver as u128),
+            readset,
+            writeset,
+            commutes,
+            readvers: SortedVec::default(),
+            snapshot,
+            ..Default::default()
+        },
+        ver,
+    }
+}
+
+#[test]
+fn commutes_do_not_conflict() {
+    let mut examiner = Examiner::new();
+    assert!(examiner.assess(counter(1, vec![], vec!["c".into()], vec![], 0)).is_commit());
+
+    // concurrent increments neither abort nor raise each other's safepoints above the overwrite
+    for ver in 2..=4 {
+        let outcome = examiner.assess(counter(ver, vec!["x".into()], vec![], vec!["c".into()], 1));
+        assert_eq!(Commit {safepoint: 1, discord: Permissive}, outcome);
+    }
+    assert_eq!(Commit {safepoint: 1, discord: Assertive}, examiner.assess(counter(5, vec![], vec![], vec!["c".into()], 0)));
+
+    // but an overwrite is ordered after them
+    let (outcome, explanation) = examiner.assess_explained(counter(6, vec![], vec!["c".into()], vec![], 0));
+    assert_eq!(Commit {safepoint: 5, discord: Assertive}, outcome);
+    assert_eq!(vec![(WriteWrite, "c".into(), 1), (WriteWrite, "c".into(), 5)], explanation.intersections);
+
+    // and an increment after it
+    assert_eq!(Commit {safepoint: 6, discord: Assertive}, examiner.assess(counter(7, vec![], vec![], vec!["c".into()], 0)));
+}
+
+#[test]
+fn commutes_conflict_with_reads() {
+    let mut examiner = Examiner::new();
+    assert!(examiner.assess(counter(1, vec!["c".into()], vec![], vec![], 0)).is_commit());
+    // an increment is ordered after a read of its key
+    assert_eq!(Commit {safepoint: 1, discord: Assertive}, examiner.assess(counter(2, vec![], vec![], vec!["c".into()], 0)));
+
+    // a read that missed the increment is antidependent, even if it saw the increment's version,
+    // since that does not imply that it saw every increment before it
+    let mut reader = counter(3, vec!["c".into()], vec![], vec![], 1);
+    reader.rec.readvers = vec![2].into();
+    assert_eq!(Abort {reason: Antidependency(2), discord: Assertive, refresh: None}, examiner.peek(&reader));
+    let (outcome, detail) = examiner.assess_detailed(reader);
+    assert_eq!(Abort {reason: Antidependency(2), discord: Assertive, refresh: None}, outcome);
+    assert_eq!(Some(AbortDetail::Antidependency {conflicts: vec![("c".into(), 2)]}), detail);
+
+    // as is a scan
+    let mut scanner = counter(4, vec![], vec![], vec![], 1);
+    scanner.rec.scans = vec![Scan::prefix("")];
+    assert_eq!(Abort {reason: Phantom {scan: 0, ver: 2}, discord: Assertive, refresh: None}, examiner.assess(scanner));
+
+    // a read that saw it is not
+    assert_eq!(Commit {safepoint: 2, discord: Permissive}, examiner.assess(counter(5, vec!["c".into()], vec![], vec![], 2)));
+}
+
+#[test]
+fn commutes_snapshot() {
+    let mut examiner = Examiner::new().with_isolation(Snapshot);
+    assert!(examiner.assess(counter(1, vec![], vec![], vec!["c".into()], 0)).is_commit());
+    assert!(examiner.assess(counter(2, vec!["x".into()], vec![], vec!["c".into()], 0)).is_commit());
+    // an overwrite that missed an increment would lose it
+    assert_eq!(Abort {reason: WriteConflict(2), discord: Assertive, refresh: None}, examiner.assess(counter(3, vec!["x".into()], vec!["c".into()], vec![], 1)));
+}
+
+#[test]
+fn commutes_discard_and_restore() {
+    let candidates = random_candidates(0, &items(6), 0.5, 200);
+    let mut examiner = Examiner::new();
+    let discard = |examiner: &mut Examiner, candidate: &Candidate| {
+        let mut entry = candidate.truncated();
+        entry.writeset.extend(candidate.rec.commutes.iter().cloned());
+        examiner.discard(entry);
+    };
+    for (index, candidate) in candidates.iter().enumerate().take(100) {
+        examiner.assess(candidate.clone());
+        if index >= 16 {
+            discard(&mut examiner, &candidates[index - 16]);
+        }
+    }
+    let mut restored: Examiner = Examiner::restore(&examiner.checkpoint()).unwrap();
+    assert_eq!(examiner.commutes, restored.commutes);
+    for candidate in &candidates[100..] {
+        assert_eq!(examiner.assess(candidate.clone()), restored.assess(candidate.clone()));
+    }
+    for candidate in &candidates[84..] {
+        discard(&mut examiner, candidate);
+    }
+    assert!(examiner.commutes.is_empty() && examiner.writes.is_empty());
+}
+
+#[test]
+fn refresh_hints_staleness() {
+    let mut examiner = Examiner::new().with_refresh_hints();
+    examiner.base = 15;
+    let mut stale = counter(16, vec!["x".into(), "y".into()], vec!["z".into()], vec![], 10);
+    stale.rec.scans = vec![Scan::prefix("w")];
+    let refresh = Refresh {snapshot: 14, reads: vec![0, 1], scans: vec![0]};
+    assert_eq!(Abort {reason: Staleness, discord: Permissive, refresh: Some(refresh)}, examiner.assess(stale));
+}
+
+#[test]
+fn refresh_hints_antidependency() {
+    let mut examiner = Examiner::new().with_refresh_hints();
+    assert!(examiner.assess(counter(1, vec![], vec!["x".into()], vec![], 0)).is_commit());
+    assert!(examiner.assess(counter(2, vec![], vec!["y".into()], vec![], 0)).is_commit());
+    assert!(examiner.assess(counter(3, vec![], vec!["z".into()], vec![], 0)).is_commit());
+
+    // only the reads that missed a write need repeating, from a snapshot that includes them all
+    let mut reader = counter(4, vec!["w".into(), "x".into(), "z".into()], vec![], vec![], 0);
+    reader.rec.readvers = vec![1].into();
+    reader.rec.scans = vec![Scan::prefix("x"), Scan::prefix("y")];
+    let refresh = Refresh {snapshot: 3, reads: vec![2], scans: vec![1]};
+    assert_eq!(Abort {reason: Antidependency(3), discord: Assertive, refresh: Some(refresh)}, examiner.assess(reader));
+
+    // retrying from the hinted snapshot commits
+    let retry = counter(5, vec!["w".into(), "x".into(), "z".into()], vec![], vec![], 3);
+    assert!(examiner.assess(retry).is_commit());
+}
+
+#[test]
+fn digests_agree_across_replicas() {
+    let candidates = random_candidates(0, &items(8), 0.0, 120);
+    let mut examiner = Examiner::new();
+    let mut replica = Examiner::new();
+    let (mut ours, mut theirs) = (vec![], vec![]);
+    for (index, candidate) in candidates.iter().enumerate() {
+        examiner.assess(candidate.clone());
+        ours.push(examiner.digest());
+        replica.assess(candidate.clone());
+        theirs.push(replica.digest());
+        if index == 60 {
+            // a restored replica carries on the same trail
+            replica = Examiner::restore(&replica.checkpoint()).unwrap();
+        }
+    }
+    assert_eq!(ours, theirs);
+    assert_eq!(candidates.last().unwrap().ver, examiner.digest().ver);
+    assert_eq!(None, Digest::first_divergence(&ours, &theirs));
+    assert_eq!(None, Digest::first_divergence(&ours, &theirs[..60]));
+}
+
+// the digest is specified over whole words, so that these values hold on every platform
+#[test]
+fn digests_are_stable() {
+    let digest = Digest::default()
+        .fold(1, &Commit {safepoint: 0, discord: Assertive})
+        .fold(2, &Abort {reason: Phantom {scan: 1, ver: 1}, discord: Assertive, refresh: None})
+        .fold(3, &Abort {reason: Staleness, discord: Permissive, refresh: None});
+    assert_eq!(Digest {ver: 3, value: 1191704613318775111}, digest);
+}
+
+#[test]
+fn digests_pinpoint_divergence() {
+    for seed in 0..5 {
+        let candidates = random_candidates(seed, &items(8), 0.0, 120);
+        let mut examiner = Examiner::new();
+        // forcing the base forward turns some commits into staleness aborts, all of them permissive
+        let mut capped = Examiner::new().with_max_keys(6);
+        let (mut ours, mut theirs, mut first_differing) = (vec![], vec![], None);
+        for candidate in candidates {
+            let ver = candidate.ver;
+            let outcome = examiner.assess(candidate.clone());
+            let capped_outcome = capped.assess(candidate);
+            if outcome != capped_outcome && first_differing.is_none() {
+                first_differing = Some(ver);
+            }
+            ours.push(examiner.digest());
+            theirs.push(capped.digest());
+        }
+        assert!(first_differing.is_some(), "seed {}", seed);
+        assert_eq!(first_differing, Digest::first_divergence(&ours, &theirs), "seed {}", seed);
+    }
+}
+
+#[test]
+fn validate_agrees_with_suffix() {
+    for seed in 0..5 {
+        let mut certifier = Certifier { examiner: Examiner::new(), suffix: Suffix::default() };
+        for candidate in random_candidates(seed, &items(8), 0.0, 150) {
+            certifier.certify(candidate);
+            assert_eq!(Vec::<Discrepancy>::new(), certifier.examiner.validate(&certifier.suffix), "seed {}", seed);
+        }
+    }
+
+    // including where the examiner forgets entries that the suffix retains
+    let mut certifier = Certifier { examiner: Examiner::new().with_max_keys(6), suffix: Suffix::default() };
+    for candidate in random_candidates(0, &items(8), 0.0, 150) {
+        certifier.certify(candidate);
+        assert_eq!(Vec::<Discrepancy>::new(), certifier.examiner.validate(&certifier.suffix));
+    }
+    assert!(certifier.examiner.metrics().forced_advances > 0);
+}
+
+#[test]
+fn validate_reports_discrepancies() {
+    let mut examiner = Examiner::new();
+    let mut suffix = Suffix::default();
+    suffix.append(vec!["x".into()], vec!["y".into()], 1);
+    examiner.learn(counter(1, vec!["x".into()], vec!["y".into()], vec![], 0));
+    assert!(examiner.validate(&suffix).is_empty());
+
+    // learned but never appended
+    examiner.learn(counter(2, vec![], vec![], vec!["c".into()], 0));
+    assert_eq!(vec![Discrepancy::Unretained {table: Table::Commutes, key: "c".into(), ver: 2}], examiner.validate(&suffix));
+
+    // appended but never learned
+    suffix.append(vec![], vec!["c".into()], 2);
+    suffix.append(vec!["z".into()], vec![], 3);
+    assert_eq!(vec![Discrepancy::Unlearned {table: Table::Reads, key: "z".into(), ver: 3}], examiner.validate(&suffix));
+    examiner.learn(counter(3, vec!["z".into()], vec![], vec![], 0));
+
+    // truncated but never discarded
+    suffix.complete(1);
+    let truncated = suffix.truncate(1, 1).unwrap().collect::<Vec<_>>();
+    assert_eq!(1, truncated.len());
+    let mut discrepancies = examiner.validate(&suffix);
+    discrepancies.sort_by_key(|discrepancy| format!("{:?}", discrepancy));
+    assert_eq!(vec![
+        Discrepancy::Unretained {table: Table::Reads, key: "x".into(), ver: 1},
+        Discrepancy::Unretained {table: Table::Writes, key: "y".into(), ver: 1},
+    ], discrepancies);
+
+    // discarded below a version it still holds
+    examiner.base = 2;
+    let mut discrepancies = examiner.validate(&suffix);
+    discrepancies.sort_by_key(|discrepancy| format!("{:?}", discrepancy));
+    assert_eq!(vec![
+        Discrepancy::BelowBase {table: Table::Reads, key: "x".into(), ver: 1},
+        Discrepancy::BelowBase {table: Table::Writes, key: "y".into(), ver: 1},
+    ], discrepancies);
+    examiner.base = 1;
+    examiner.discard(truncated.into_iter().next().unwrap());
+    assert!(examiner.validate(&suffix).is_empty());
+}
+
+#[test]
+fn resolve_votes() {
+    let mut examiner = Examiner::new();
+    assert!(examiner.assess(counter(1, vec![], vec!["x".into()], vec![], 0)).is_commit());
+
+    // the vote at 2 is learned provisionally, so a read of 'x' from before it aborts
+    let vote = examiner.vote(&counter(2, vec!["x".into()], vec!["x".into(), "y".into()], vec![], 1).rec, 2);
+    assert!(vote.is_commit());
+    assert!(examiner.assess(counter(3, vec!["x".into()], vec![], vec![], 1)).is_abort());
+    assert!(examiner.vote(&counter(4, vec![], vec!["y".into()], vec![], 3).rec, 4).is_commit());
+
+    // 'x' reverts to its write at 1, while 'y' was since replaced by the vote at 4
+    let abort = Outcome::combine(&[vote, Abort { reason: Staleness, discord: Permissive, refresh: None }]);
+    examiner.resolve(2, abort.is_commit());
+    assert_eq!(Some(&1), examiner.writes.get(&"x".into()));
+    assert_eq!(Some(&3), examiner.reads.get(&"x".into()));
+    assert_eq!(Some(&4), examiner.writes.get(&"y".into()));
+    assert!(examiner.assess(counter(5, vec!["x".into()], vec![], vec![], 1)).is_commit());
+
+    // as the vote at 2 was rolled back, that at 4 reverts 'y' to never having been written
+    examiner.resolve(4, false);
+    assert_eq!(None, examiner.writes.get(&"y".into()));
+    assert_eq!(Err(ExaminerError::NoSuchVote { ver: 4 }), examiner.try_resolve(4, false));
+
+    let vote = examiner.vote(&counter(6, vec![], vec!["z".into()], vec![], 5).rec, 6);
+    examiner.resolve(6, Outcome::combine(&[vote]).is_commit());
+    assert_eq!(Some(&6), examiner.writes.get(&"z".into()));
+    assert!(examiner.provisional.is_empty());
+}
+
+#[test]
+fn checkpoint_restore_votes() {
+    let mut examiner = Examiner::new();
+    assert!(examiner.assess(counter(1, vec![], vec!["x".into()], vec![], 0)).is_commit());
+    assert!(examiner.vote(&counter(2, vec!["x".into()], vec!["x".into(), "y".into()], vec![], 1).rec, 2).is_commit());
+    assert!(examiner.vote(&counter(3, vec![], vec!["y".into()], vec!["z".into()], 2).rec, 3).is_commit());
+
+    // the restored examiner can still roll back the votes cast before its checkpoint
+    let mut restored: Examiner = Examiner::restore(&examiner.checkpoint()).unwrap();
+    assert_eq!(2, restored.provisional.len());
+    for examiner in [&mut examiner, &mut restored] {
+        examiner.resolve(2, false);
+        examiner.resolve(3, false);
+        assert_eq!(Some(&1), examiner.writes.get(&"x".into()));
+        assert_eq!(None, examiner.writes.get(&"y".into()));
+        assert_eq!(None, examiner.commutes.get(&"z".into()));
+        assert!(examiner.provisional.is_empty());
+    }
+    assert_eq!(examiner.reads, restored.reads);
+    assert_eq!(examiner.writes, restored.writes);
+}
+
+#[test]
+fn resolve_votes_reverts_intents() {
+    let mut examiner = Examiner::new().with_hierarchy();
+    assert!(examiner.assess(counter(1, vec![], vec!["t/1".into()], vec![], 0)).is_commit());
+    assert!(examiner.vote(&counter(2, vec![], vec!["t/1".into(), "u/1".into()], vec![], 1).rec, 2).is_commit());
+    assert!(examiner.vote(&counter(3, vec!["u/2".into()], vec![], vec![], 2).rec, 3).is_commit());
+
+    // the intent on 't' reverts to the write at 1, while that on 'u' was left by the vote at 2 alone
+    examiner.resolve(2, false);
+    assert_eq!(Some(&1), examiner.write_intents.get("t"));
+    assert_eq!(None, examiner.write_intents.get("u"));
+    assert_eq!(Some(&3), examiner.read_intents.get("u"));
+    assert!(examiner.assess(counter(4, vec!["u".into()], vec![], vec![], 1)).is_commit());
+
+    // the intents of a rolled back vote are not left for its retracted entry to discard
+    examiner.resolve(3, false);
+    examiner.discard(counter(1, vec![], vec!["t/1".into()], vec![], 0).truncated());
+    examiner.discard(TruncatedEntry { ver: 3, readset: vec![], writeset: vec![] });
+    examiner.discard(counter(4, vec!["u".into()], vec![], vec![], 1).truncated());
+    assert_eq!(0, examiner.metrics().intents);
+}
+
+#[test]
+fn combine_votes() {
+    let commit = |safepoint, discord| Commit { safepoint, discord };
+    assert_eq!(commit(7, Assertive), Outcome::combine(&[commit(3, Assertive), commit(7, Assertive)]));
+    assert_eq!(commit(7, Permissive), Outcome::combine(&[commit(7, Assertive), commit(3, Permissive)]));
+
+    let abort = |reason, discord| Abort { reason, discord, refresh: None };
+    assert_eq!(
+        Abort { reason: Staleness, discord: Assertive, refresh: None },
+        Outcome::combine(&[commit(3, Assertive), abort(Staleness, Permissive), abort(Antidependency(4), Assertive)])
+    );
+    assert_eq!(
+        Abort { reason: Antidependency(4), discord: Permissive, refresh: None },
+        Outcome::combine(&[abort(Antidependency(4), Permissive), commit(3, Assertive)])
+    );
+}
+
+#[test]
+fn key_safepoints() {
+    let mut examiner = Examiner::new();
+    assert!(examiner.assess(counter(1, vec![], vec!["x".into()], vec![], 0)).is_commit());
+    assert!(examiner.assess(counter(2, vec!["y".into()], vec!["z".into()], vec![], 1)).is_commit());
+
+    // 'x' need only follow the write at 1, while 'y' must follow the read at 2
+    let (outcome, safepoints) = examiner.assess_keyed(&counter(3, vec![], vec!["x".into(), "y".into()], vec![], 0).rec, 3);
+    assert_eq!(Commit {safepoint: 2, discord: Assertive}, outcome);
+    assert_eq!(vec![1, 2], safepoints);
+
+    // an increment of a key that is neither read nor overwritten needs follow nothing
+    let (outcome, safepoints) = examiner.assess_keyed(&counter(4, vec![], vec!["x".into()], vec!["c".into()], 0).rec, 4);
+    assert_eq!(Commit {safepoint: 3, discord: Assertive}, outcome);
+    assert_eq!(vec![3, 0], safepoints);
+
+    // each write follows the writes that the candidate read
+    let (outcome, safepoints) = examiner.assess_keyed(&counter(5, vec!["y".into()], vec!["c".into()], vec![], 4).rec, 5);
+    assert_eq!(Commit {safepoint: 4, discord: Permissive}, outcome);
+    assert_eq!(vec![4], safepoints);
+
+    let (outcome, safepoints) = examiner.assess_keyed(&counter(6, vec!["x".into()], vec!["y".into()], vec![], 2).rec, 6);
+    assert!(outcome.is_abort());
+    assert!(safepoints.is_empty());
+}
+
+#[test]
+fn query_read_only() {
+    let mut examiner = Examiner::new();
+    let query = |readset: Vec<String>, snapshot| counter(1, readset, vec![], vec![], snapshot).rec;
+    assert_eq!(Commit {safepoint: 0, discord: Permissive}, examiner.query(&query(vec!["x".into()], 0)));
+    assert!(examiner.assess(counter(1, vec![], vec!["x".into()], vec![], 0)).is_commit());
+    assert!(examiner.assess(counter(2, vec![], vec!["y".into()], vec![], 0)).is_commit());
+
+    // a query must have read the last write of each key
+    let digest = examiner.digest();
+    assert_eq!(Abort {reason: Antidependency(1), discord: Assertive, refresh: None}, examiner.query(&query(vec!["x".into()], 0)));
+    assert_eq!(Commit {safepoint: 1, discord: Permissive}, examiner.query(&query(vec!["x".into()], 1)));
+    assert_eq!(Commit {safepoint: 2, discord: Permissive}, examiner.query(&query(vec!["x".into(), "y".into()], 2)));
+
+    // but is not learned
+    assert_eq!(None, examiner.reads.get(&"x".into()));
+    assert_eq!(digest, examiner.digest());
+    assert_eq!(2, examiner.metrics().outcomes.commits());
+    assert_eq!(Err(ExaminerError::NotReadOnly), examiner.try_query(&counter(3, vec![], vec!["x".into()], vec![], 2).rec));
+
+    // a snapshot behind the base is stale
+    examiner.discard(counter(1, vec![], vec!["x".into()], vec![], 0).truncated());
+    assert_eq!(Abort {reason: Staleness, discord: Permissive, refresh: None}, examiner.query(&query(vec!["x".into()], 0)));
+    assert_eq!(Commit {safepoint: 1, discord: Permissive}, examiner.query(&query(vec!["x".into()], 1)));
+}
+
+#[test]
+fn precise_aborts() {
+    let mut examiner = Examiner::new().with_precise_aborts();
+    let mut imprecise = Examiner::new();
+    let candidates = [
+        counter(1, vec![], vec!["x".into()], vec![], 0),
+        counter(2, vec!["x".into()], vec!["y".into()], vec![], 0),
+        counter(3, vec!["y".into()], vec!["z".into()], vec![], 1),
+    ];
+    for candidate in &candidates[..2] {
+        assert_eq!(imprecise.assess(candidate.clone()), examiner.assess(candidate.clone()));
+    }
+
+    // an assertive abort is not learned, so it neither aborts nor holds back the candidates after it
+    assert_eq!(None, examiner.writes.get(&"y".into()));
+    assert_eq!(Abort {reason: Antidependency(2), discord: Assertive, refresh: None}, imprecise.assess(candidates[2].clone()));
+    assert_eq!(Commit {safepoint: 0, discord: Permissive}, examiner.assess(candidates[2].clone()));
+
+    // a permissive abort is learned as uncertain, and aborts only permissively unless a certain
+    // write is also missed
+    examiner.discard(candidates[0].truncated());
+    let outcome = examiner.assess(counter(4, vec!["x".into()], vec!["w".into()], vec![], 0));
+    assert_eq!(Abort {reason: Staleness, discord: Permissive, refresh: None}, outcome);
+    let outcome = examiner.assess(counter(5, vec!["w".into()], vec!["v".into()], vec![], 3));
+    assert_eq!(Abort {reason: Antidependency(4), discord: Permissive, refresh: None}, outcome);
+    let outcome = examiner.assess(counter(6, vec!["w".into(), "z".into()], vec![], vec![], 2));
+    assert_eq!(Abort {reason: Antidependency(4), discord: Assertive, refresh: None}, outcome);
+
+    // the unlearned entries are discarded all the same
+    examiner.discard(candidates[1].truncated());
+    examiner.discard(candidates[2].truncated());
+    examiner.discard(counter(4, vec!["x".into()], vec!["w".into()], vec![], 0).truncated());
+    examiner.discard(counter(5, vec!["w".into()], vec!["v".into()], vec![], 3).truncated());
+    examiner.discard(counter(6, vec!["w".into(), "z".into()], vec![], vec![], 2).truncated());
+    assert_eq!(Some(7), examiner.base());
+    assert!(examiner.uncertain.is_empty() && examiner.unlearned.is_empty());
+}
+
+#[test]
+fn precise_aborts_agree_with_suffix() {
+    for seed in 0..5 {
+        let mut certifier = Certifier { examiner: Examiner::new().with_precise_aborts(), suffix: Suffix::default() };
+        for candidate in random_candidates(seed, &items(8), 0.0, 150) {
+            let expected = certifier.examiner.peek(&candidate);
+            assert_eq!(expected, certifier.certify(candidate), "seed {}", seed);
+            assert_eq!(Vec::<Discrepancy>::new(), certifier.examiner.validate(&certifier.suffix), "seed {}", seed);
+        }
+    }
+}
+
+#[test]
+fn precise_aborts_restore_mid_stream() {
+    for seed in 0..5 {
+        let candidates = random_candidates(seed, &items(8), 0.0, 120);
+        let expected = assess_with_discards(&mut Examiner::new().with_precise_aborts(), &candidates, 0);
+        for split in (0..candidates.len()).step_by(13) {
+            let mut examiner = Examiner::new().with_precise_aborts();
+            let mut outcomes = assess_with_discards(&mut examiner, &candidates[..split], 0);
+            let mut restored: Examiner = Examiner::restore(&examiner.checkpoint()).unwrap();
+            outcomes.extend(assess_with_discards(&mut restored, &candidates, split));
+            assert_eq!(expected, outcomes, "seed {}, split {}", seed, split);
+        }
+    }
+}
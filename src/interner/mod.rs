@@ -0,0 +1,178 @@
+use crate::examiner::{Key, Record};
+use rustc_hash::FxHashMap;
+use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+
+// a compact stand-in for an interned key; only meaningful to the interner that issued it
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Sym(u32);
+
+impl Sym {
+    pub fn id(self) -> u32 {
+        self.0
+    }
+}
+
+// a release of a symbol that holds no outstanding references, or a record that cannot be
+// interned, rejected without altering the interner
+#[derive(Debug, PartialEq, Clone)]
+pub enum InternerError {
+    NoSuchSymbol(Sym),
+    Scans, // symbols are not ordered by key, so scans cannot be interned
+}
+
+impl Display for InternerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InternerError::NoSuchSymbol(sym) => write!(f, "no such symbol {:?}", sym),
+            InternerError::Scans => write!(f, "scans cannot be interned"),
+        }
+    }
+}
+
+impl std::error::Error for InternerError {}
+
+#[derive(Debug)]
+struct Slot<K> {
+    key: Rc<K>,
+    refs: usize, // how many interned references are outstanding
+}
+
+// A reference-counted symbol table. Every call to intern() takes out a reference to the key's
+// symbol, which must be balanced by a call to release(); the key is dropped (and its symbol
+// recycled) when the last reference is released. Each key is stored once, shared between the
+// lookup table and its slot. Symbols are ordered by issue, not by key, so scans cannot be
+// evaluated over interned keys.
+#[derive(Debug)]
+pub struct Interner<K> {
+    syms: FxHashMap<Rc<K>, Sym>,
+    slots: Vec<Option<Slot<K>>>,
+    free: Vec<u32>,
+}
+
+impl<K> Default for Interner<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K> Interner<K> {
+    pub fn new() -> Self {
+        Self {
+            syms: FxHashMap::default(),
+            slots: vec![],
+            free: vec![],
+        }
+    }
+
+    // the number of distinct keys presently interned
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<K: Key> Interner<K> {
+    pub fn intern(&mut self, key: &K) -> Sym {
+        if let Some(&sym) = self.syms.get(key) {
+            self.slots[sym.0 as usize].as_mut().unwrap().refs += 1;
+            return sym;
+        }
+
+        let key = Rc::new(key.clone());
+        let slot = Slot { key: Rc::clone(&key), refs: 1 };
+        let sym = match self.free.pop() {
+            Some(id) => {
+                self.slots[id as usize] = Some(slot);
+                Sym(id)
+            }
+            None => {
+                assert!(self.slots.len() < u32::MAX as usize, "symbol space exhausted");
+                self.slots.push(Some(slot));
+                Sym((self.slots.len() - 1) as u32)
+            }
+        };
+        self.syms.insert(key, sym);
+        sym
+    }
+
+    pub fn intern_all(&mut self, keys: &[K]) -> Vec<Sym> {
+        keys.iter().map(|key| self.intern(key)).collect()
+    }
+
+    // interns the keys of the given record, producing its symbolic equivalent
+    pub fn intern_record(&mut self, rec: &Record<K>) -> Record<Sym> {
+        self.try_intern_record(rec).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_intern_record(&mut self, rec: &Record<K>) -> Result<Record<Sym>, InternerError> {
+        if !rec.scans.is_empty() {
+            return Err(InternerError::Scans);
+        }
+        Ok(Record {
+            xid: rec.xid,
+            readset: self.intern_all(&rec.readset),
+            scans: vec![],
+            writeset: self.intern_all(&rec.writeset),
+            commutes: self.intern_all(&rec.commutes),
+            readvers: rec.readvers.clone(),
+            snapshot: rec.snapshot,
+        })
+    }
+
+    pub fn resolve(&self, sym: Sym) -> Option<&K> {
+        match self.slots.get(sym.0 as usize) {
+            Some(Some(slot)) => Some(&slot.key),
+            _ => None,
+        }
+    }
+
+    pub fn release(&mut self, sym: Sym) {
+        self.try_release(sym).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_release(&mut self, sym: Sym) -> Result<(), InternerError> {
+        self.try_release_all(&[sym])
+    }
+
+    pub fn release_all(&mut self, syms: &[Sym]) {
+        self.try_release_all(syms).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    // releases every symbol given, or none if any of them is not held as often as it is given
+    pub fn try_release_all(&mut self, syms: &[Sym]) -> Result<(), InternerError> {
+        self.check_release_all(syms)?;
+        for &sym in syms {
+            let slot = self.slots[sym.0 as usize].as_mut().unwrap();
+            slot.refs -= 1;
+            if slot.refs == 0 {
+                let slot = self.slots[sym.0 as usize].take().unwrap();
+                self.syms.remove(&slot.key);
+                self.free.push(sym.0);
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn check_release_all(&self, syms: &[Sym]) -> Result<(), InternerError> {
+        let mut releases = FxHashMap::<Sym, usize>::default();
+        for &sym in syms {
+            let held = match self.slots.get(sym.0 as usize) {
+                Some(Some(slot)) => slot.refs,
+                _ => 0,
+            };
+            let released = releases.entry(sym).or_insert(0);
+            *released += 1;
+            if *released > held {
+                return Err(InternerError::NoSuchSymbol(sym));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests;
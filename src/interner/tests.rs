@@ -0,0 +1,111 @@
+use crate::interner::{Interner, InternerError, Sym};
+use crate::examiner::{Record, Scan};
+use crate::sortedvec::SortedVec;
+use uuid::Uuid;
+
+#[test]
+fn intern_resolve_release() {
+    let mut interner = Interner::new();
+    assert!(interner.is_empty());
+    let x = interner.intern(&"x".to_string());
+    let y = interner.intern(&"y".to_string());
+    assert_ne!(x, y);
+    assert_eq!(x, interner.intern(&"x".to_string()));
+    assert_eq!(2, interner.len());
+    assert_eq!(Some(&"x".to_string()), interner.resolve(x));
+    assert_eq!(Some(&"y".to_string()), interner.resolve(y));
+
+    // 'x' was interned twice, so it survives the first release
+    interner.release(x);
+    assert_eq!(Some(&"x".to_string()), interner.resolve(x));
+    interner.release(x);
+    assert_eq!(None, interner.resolve(x));
+    assert_eq!(1, interner.len());
+
+    interner.release(y);
+    assert_eq!(None, interner.resolve(y));
+    assert!(interner.is_empty());
+}
+
+#[test]
+fn recycle_released_syms() {
+    let mut interner = Interner::new();
+    let x = interner.intern(&"x".to_string());
+    interner.release(x);
+    let z = interner.intern(&"z".to_string());
+    assert_eq!(x.id(), z.id());
+    assert_eq!(Some(&"z".to_string()), interner.resolve(z));
+    assert_eq!(z, interner.intern(&"z".to_string()));
+    assert_ne!(z, interner.intern(&"x".to_string()));
+}
+
+#[test]
+fn intern_record() {
+    let mut interner = Interner::new();
+    let rec = Record {
+        xid: Uuid::from_u128(1),
+        readset: vec!["x".to_string(), "y".to_string()],
+        writeset: vec!["y".to_string()],
+        readvers: vec![3].into(),
+        snapshot: 2,
+        ..Default::default()
+    };
+    let interned = interner.intern_record(&rec);
+    assert_eq!(rec.xid, interned.xid);
+    assert_eq!(rec.readvers, interned.readvers);
+    assert_eq!(rec.snapshot, interned.snapshot);
+    assert_eq!(interned.readset[1], interned.writeset[0]);
+    assert_eq!(2, interner.len());
+
+    interner.release_all(&interned.readset);
+    assert_eq!(1, interner.len());
+    interner.release_all(&interned.writeset);
+    assert!(interner.is_empty());
+}
+
+#[test] #[should_panic(expected = "no such symbol Sym(0)")]
+fn release_unknown() {
+    Interner::<String>::new().release(Sym(0));
+}
+
+#[test]
+fn try_release_unheld() {
+    let mut interner = Interner::new();
+    assert_eq!(Err(InternerError::NoSuchSymbol(Sym(0))), interner.try_release(Sym(0)));
+
+    // a symbol released more often than it is held is rejected without releasing the others
+    let (x, y) = (interner.intern(&"x".to_string()), interner.intern(&"y".to_string()));
+    assert_eq!(Err(InternerError::NoSuchSymbol(x)), interner.try_release_all(&[y, x, x]));
+    assert_eq!(2, interner.len());
+    assert_eq!(Ok(()), interner.try_release_all(&[y, x]));
+    assert!(interner.is_empty());
+}
+
+#[test] #[should_panic(expected = "scans cannot be interned")]
+fn intern_record_with_scans() {
+    Interner::new().intern_record(&Record {
+        xid: Uuid::from_u128(1),
+        readset: vec![],
+        scans: vec![Scan::prefix("x")],
+        writeset: vec![],
+        readvers: SortedVec::default(),
+        snapshot: 0,
+        ..Default::default()
+    });
+}
+
+#[test]
+fn try_intern_record_with_scans() {
+    let mut interner = Interner::new();
+    let rec = Record {
+        xid: Uuid::from_u128(1),
+        readset: vec!["x".to_string()],
+        scans: vec![Scan::prefix("x")],
+        writeset: vec![],
+        readvers: SortedVec::default(),
+        snapshot: 0,
+        ..Default::default()
+    };
+    assert_eq!(Some(InternerError::Scans), interner.try_intern_record(&rec).err());
+    assert!(interner.is_empty());
+}
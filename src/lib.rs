@@ -1,4 +1,6 @@
+pub mod checkpoint;
 pub mod examiner;
 pub mod havoc;
+pub mod interner;
 pub mod suffix;
 pub mod sortedvec;
\ No newline at end of file
@@ -1,26 +1,51 @@
+use crate::checkpoint::{CheckpointError, Codec, Reader, Writer};
+use crate::examiner::{Key, Learning, Record, Scan};
 use crate::suffix::AppendSkipReason::Nonmonotonic;
 use std::ops::Range;
 use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
 
-#[derive(Debug, PartialEq)]
-pub struct RetainedEntry {
-    pub readset: Vec<String>,
-    pub writeset: Vec<String>,
+#[derive(Debug, PartialEq, Clone)]
+pub struct RetainedEntry<K = String> {
+    pub readset: Vec<K>,
+    pub writeset: Vec<K>,
+    pub commutes: Vec<K>,   // retained apart from the writes when appended as a record
+    pub scans: Vec<Scan<K>>,
+    pub learning: Learning, // certain unless set otherwise by a precise certifier
     pub completed: bool,
 }
 
-#[derive(Debug, PartialEq)]
-pub struct TruncatedEntry {
+#[derive(Debug, PartialEq, Clone)]
+pub struct TruncatedEntry<K = String> {
     pub ver: u64,
-    pub readset: Vec<String>,
-    pub writeset: Vec<String>,
+    pub readset: Vec<K>,
+    pub writeset: Vec<K>,
 }
 
 #[derive(Debug)]
-pub struct Suffix {
+pub struct Suffix<K = String> {
     base: u64,
-    entries: VecDeque<Option<RetainedEntry>>,
+    entries: VecDeque<Option<RetainedEntry<K>>>,
     highest_completed: u64,
+    num_retained: usize,   // entries (not gaps) in 'entries'
+    num_incomplete: usize, // retained entries yet to be completed
+    truncations: u64,
+    truncated: u64,
+    last_truncated: u64, // the version of the last entry truncated, excluding gaps
+}
+
+// a point-in-time snapshot of the suffix's occupancy and cumulative counts
+#[derive(Debug, PartialEq, Clone)]
+pub struct SuffixMetrics {
+    pub lwm: Option<u64>,
+    pub hwm: Option<u64>,
+    pub highest_completed: Option<u64>,
+    pub retained: usize,     // entries held, excluding gaps
+    pub incomplete: usize,   // entries held that are yet to be completed
+    pub decision_lag: u64,   // offsets between the highest completed and the hwm
+    pub truncation_lag: u64, // completed offsets between the lwm and the highest completed
+    pub truncations: u64,    // calls to truncate() that removed at least one offset
+    pub truncated: u64,      // entries truncated, excluding gaps
 }
 
 impl Default for Suffix {
@@ -53,15 +78,53 @@ pub enum CompleteSkipReason {
     NoSuchCandidate,
 }
 
+// a malformed request, rejected without altering the suffix
+#[derive(Debug, PartialEq, Clone)]
+pub enum SuffixError {
+    UnsupportedVersion,
+    Uninitialized,
+    InvalidExtents { min_extent: usize, max_extent: usize },
+}
+
+impl Display for SuffixError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SuffixError::UnsupportedVersion => write!(f, "unsupported version 0"),
+            SuffixError::Uninitialized => write!(f, "uninitialized suffix"),
+            SuffixError::InvalidExtents { min_extent, max_extent } => write!(
+                f,
+                "invalid min_extent ({}), max_extent ({})",
+                min_extent, max_extent
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SuffixError {}
+
 impl Suffix {
     pub fn new(capacity: usize) -> Self {
+        Self::with_capacity(capacity)
+    }
+}
+
+impl<K> Suffix<K> {
+    // as new(), for keys other than strings
+    pub fn with_capacity(capacity: usize) -> Self {
         Self {
             base: 0,
             entries: VecDeque::with_capacity(capacity),
             highest_completed: 0,
+            num_retained: 0,
+            num_incomplete: 0,
+            truncations: 0,
+            truncated: 0,
+            last_truncated: 0,
         }
     }
+}
 
+impl<K: Key> Suffix<K> {
     pub fn lwm(&self) -> Option<u64> {
         match self.base {
             0 => None,
@@ -83,13 +146,67 @@ impl Suffix {
         }
     }
 
+    pub fn last_truncated(&self) -> Option<u64> {
+        match self.last_truncated {
+            0 => None,
+            last_truncated => Some(last_truncated),
+        }
+    }
+
     pub fn append(
         &mut self,
-        readset: Vec<String>,
-        writeset: Vec<String>,
+        readset: Vec<K>,
+        writeset: Vec<K>,
         ver: u64,
     ) -> AppendResult {
-        assert_ne!(0, ver, "unsupported version 0");
+        self.try_append(readset, writeset, ver)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_append(
+        &mut self,
+        readset: Vec<K>,
+        writeset: Vec<K>,
+        ver: u64,
+    ) -> Result<AppendResult, SuffixError> {
+        self.try_append_entry(
+            RetainedEntry {
+                readset,
+                writeset,
+                commutes: vec![],
+                scans: vec![],
+                learning: Learning::Certain,
+                completed: false,
+            },
+            ver,
+        )
+    }
+
+    // as append(), retaining the record's commutative writes and scans apart from its other
+    // writes, so that an examiner rebuilt from the suffix treats them as it did when assessed
+    pub fn append_record(&mut self, rec: Record<K>, ver: u64) -> AppendResult {
+        self.try_append_record(rec, ver)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_append_record(&mut self, rec: Record<K>, ver: u64) -> Result<AppendResult, SuffixError> {
+        self.try_append_entry(
+            RetainedEntry {
+                readset: rec.readset,
+                writeset: rec.writeset,
+                commutes: rec.commutes,
+                scans: rec.scans,
+                learning: Learning::Certain,
+                completed: false,
+            },
+            ver,
+        )
+    }
+
+    fn try_append_entry(&mut self, entry: RetainedEntry<K>, ver: u64) -> Result<AppendResult, SuffixError> {
+        if ver == 0 {
+            return Err(SuffixError::UnsupportedVersion);
+        }
         if self.base == 0 {
             // initialize the base offset and highest completed on the first inserted entry
             self.base = ver;
@@ -98,7 +215,7 @@ impl Suffix {
 
         let hwm = self.base + self.entries.len() as u64;
         if ver < hwm {
-            return AppendResult::Skipped(Nonmonotonic);
+            return Ok(AppendResult::Skipped(Nonmonotonic));
         }
 
         let pad = (ver - hwm) as usize;
@@ -106,16 +223,14 @@ impl Suffix {
         for _ in (0..pad).into_iter() {
             self.entries.push_back(None)
         }
-        self.entries.push_back(Some(RetainedEntry {
-            readset,
-            writeset,
-            completed: false,
-        }));
+        self.entries.push_back(Some(entry));
+        self.num_retained += 1;
+        self.num_incomplete += 1;
 
-        AppendResult::Appended
+        Ok(AppendResult::Appended)
     }
 
-    pub fn get(&self, ver: u64) -> Option<&RetainedEntry> {
+    pub fn get(&self, ver: u64) -> Option<&RetainedEntry<K>> {
         if self.base == 0 || ver < self.base {
             return None;
         }
@@ -131,6 +246,41 @@ impl Suffix {
         };
     }
 
+    // Clears the keys of a retained entry, returning them, e.g., once a candidate spanning
+    // partitions aborts and the examiner rolls back its provisional reads and writes. The entry
+    // remains, to be completed and truncated as any other. Commutative writes are returned among
+    // the writes; scans are dropped.
+    pub fn retract(&mut self, ver: u64) -> Option<(Vec<K>, Vec<K>)> {
+        if self.base == 0 || ver < self.base {
+            return None;
+        }
+        match self.entries.get_mut((ver - self.base) as usize) {
+            Some(Some(entry)) => {
+                let mut writeset = std::mem::take(&mut entry.writeset);
+                writeset.append(&mut entry.commutes);
+                entry.scans.clear();
+                Some((std::mem::take(&mut entry.readset), writeset))
+            }
+            _ => None,
+        }
+    }
+
+    // Records how the examiner learned a retained entry, e.g., Examiner::learning() once assessed
+    // in precise mode, so that an examiner rebuilt from the suffix learns it alike. Returns false
+    // if the entry is not retained.
+    pub fn set_learning(&mut self, ver: u64, learning: Learning) -> bool {
+        if self.base == 0 || ver < self.base {
+            return false;
+        }
+        match self.entries.get_mut((ver - self.base) as usize) {
+            Some(Some(entry)) => {
+                entry.learning = learning;
+                true
+            }
+            _ => false,
+        }
+    }
+
     pub fn complete(&mut self, ver: u64) -> CompleteResult {
         if self.base == 0 {
             return CompleteResult::Skipped(CompleteSkipReason::Uninitialized);
@@ -146,7 +296,12 @@ impl Suffix {
 
         match &mut self.entries[index] {
             None => return CompleteResult::Skipped(CompleteSkipReason::NoSuchCandidate),
-            Some(item) => item.completed = true,
+            Some(item) => {
+                if !item.completed {
+                    item.completed = true;
+                    self.num_incomplete -= 1;
+                }
+            }
         }
 
         if ver == self.highest_completed + 1 {
@@ -175,26 +330,64 @@ impl Suffix {
         }
     }
 
+    pub fn metrics(&self) -> SuffixMetrics {
+        let (decision_lag, truncation_lag) = match self.base {
+            0 => (0, 0),
+            base => {
+                let hwm = base + self.entries.len() as u64;
+                (hwm - 1 - self.highest_completed, self.highest_completed + 1 - base)
+            }
+        };
+        SuffixMetrics {
+            lwm: self.lwm(),
+            hwm: self.hwm(),
+            highest_completed: self.highest_completed(),
+            retained: self.num_retained,
+            incomplete: self.num_incomplete,
+            decision_lag,
+            truncation_lag,
+            truncations: self.truncations,
+            truncated: self.truncated,
+        }
+    }
+
     pub fn truncate(
         &mut self,
         min_extent: usize,
         max_extent: usize,
-    ) -> Option<impl Iterator<Item = TruncatedEntry> + '_> {
-        assert_ne!(self.base, 0, "uninitialized suffix");
-        assert!(min_extent > 0, "invalid min_extent ({})", min_extent);
-        assert!(
-            max_extent >= min_extent,
-            "invalid min_extent ({}), max_extent ({})",
-            min_extent,
-            max_extent
-        );
+    ) -> Option<impl Iterator<Item = TruncatedEntry<K>> + '_> {
+        self.try_truncate(min_extent, max_extent)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_truncate(
+        &mut self,
+        min_extent: usize,
+        max_extent: usize,
+    ) -> Result<Option<impl Iterator<Item = TruncatedEntry<K>> + '_>, SuffixError> {
+        if self.base == 0 {
+            return Err(SuffixError::Uninitialized);
+        }
+        if min_extent == 0 || max_extent < min_extent {
+            return Err(SuffixError::InvalidExtents { min_extent, max_extent });
+        }
 
         if self.entries.len() <= max_extent {
-            return None;
+            return Ok(None);
         }
         let base = self.base;
         let overhang = (self.highest_completed + 1 - base) as usize;
         let num_to_truncate = std::cmp::min(self.entries.len() - min_extent, overhang);
+        // entries are only truncated once completed
+        let num_truncated = self.entries.range(..num_to_truncate).filter(|entry| entry.is_some()).count();
+        if let Some(index) = self.entries.range(..num_to_truncate).rposition(|entry| entry.is_some()) {
+            self.last_truncated = base + index as u64;
+        }
+        self.num_retained -= num_truncated;
+        self.truncated += num_truncated as u64;
+        if num_to_truncate > 0 {
+            self.truncations += 1;
+        }
         let drained = self.entries.drain(..num_to_truncate);
         self.base = base + num_to_truncate as u64;
 
@@ -202,7 +395,9 @@ impl Suffix {
             .enumerate()
             .filter(|(_, entry)| entry.is_some())
             .map(move |(entry_index, entry)| {
-                let entry = entry.unwrap();
+                let mut entry = entry.unwrap();
+                // commutative writes are discarded along with the rest
+                entry.writeset.append(&mut entry.commutes);
                 TruncatedEntry {
                     ver: base + entry_index as u64,
                     readset: entry.readset,
@@ -210,7 +405,89 @@ impl Suffix {
                 }
             });
 
-        Some(truncated)
+        Ok(Some(truncated))
+    }
+}
+
+const CHECKPOINT_MAGIC: &[u8; 4] = b"STXS";
+
+impl<K: Key + Codec> Suffix<K> {
+    // a compact image of the suffix, from which an identically behaving suffix can be restored;
+    // cumulative counts are not carried over
+    pub fn checkpoint(&self) -> Vec<u8> {
+        let mut writer = Writer::new(CHECKPOINT_MAGIC);
+        writer.varint(self.base);
+        writer.varint(self.highest_completed);
+        writer.varint(self.last_truncated);
+        writer.length(self.entries.len());
+        for entry in &self.entries {
+            match entry {
+                None => writer.u8(0),
+                Some(entry) => {
+                    writer.u8(if entry.completed { 2 } else { 1 });
+                    entry.readset.encode(&mut writer);
+                    entry.writeset.encode(&mut writer);
+                    entry.commutes.encode(&mut writer);
+                    entry.scans.encode(&mut writer);
+                    writer.u8(match entry.learning {
+                        Learning::Certain => 0,
+                        Learning::Uncertain => 1,
+                        Learning::Unlearned => 2,
+                    });
+                }
+            }
+        }
+        writer.finish()
+    }
+
+    pub fn restore(checkpoint: &[u8]) -> Result<Self, CheckpointError> {
+        let mut reader = Reader::new(checkpoint, CHECKPOINT_MAGIC)?;
+        let base = reader.varint()?;
+        let highest_completed = reader.varint()?;
+        let last_truncated = reader.varint()?;
+        let len = reader.length()?;
+        let mut entries = VecDeque::with_capacity(len);
+        for _ in 0..len {
+            let completed = match reader.u8()? {
+                0 => {
+                    entries.push_back(None);
+                    continue;
+                }
+                1 => false,
+                2 => true,
+                tag => return Err(CheckpointError::InvalidTag(tag)),
+            };
+            entries.push_back(Some(RetainedEntry {
+                readset: Vec::decode(&mut reader)?,
+                writeset: Vec::decode(&mut reader)?,
+                commutes: Vec::decode(&mut reader)?,
+                scans: Vec::decode(&mut reader)?,
+                learning: match reader.u8()? {
+                    0 => Learning::Certain,
+                    1 => Learning::Uncertain,
+                    2 => Learning::Unlearned,
+                    tag => return Err(CheckpointError::InvalidTag(tag)),
+                },
+                completed,
+            }));
+        }
+        reader.finish()?;
+
+        let num_retained = entries.iter().filter(|entry| entry.is_some()).count();
+        let num_incomplete = entries
+            .iter()
+            .filter(|entry| matches!(entry, Some(entry) if !entry.completed))
+            .count();
+        Ok(Self {
+            base,
+            entries,
+            highest_completed,
+            num_retained,
+            num_incomplete,
+            truncations: 0,
+            truncated: 0,
+            last_truncated,
+        })
     }
 }
 
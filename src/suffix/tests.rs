@@ -1,5 +1,8 @@
-use crate::suffix::{Suffix, RetainedEntry, TruncatedEntry, AppendResult, CompleteResult, CompleteSkipReason};
+use crate::suffix::{Suffix, RetainedEntry, TruncatedEntry, AppendResult, CompleteResult, CompleteSkipReason, SuffixError, SuffixMetrics};
 use crate::suffix::AppendSkipReason::Nonmonotonic;
+use crate::examiner::{Learning, Record, Scan};
+use crate::sortedvec::SortedVec;
+use uuid::Uuid;
 
 impl Suffix {
     fn enumerate(&self) -> impl Iterator<Item = (u64, &Option<RetainedEntry>)> {
@@ -43,6 +46,9 @@ impl RetainedEntry {
         Self {
             readset: Z(readset).into(),
             writeset: Z(writeset).into(),
+            commutes: vec![],
+            scans: vec![],
+            learning: Learning::Certain,
             completed
         }
     }
@@ -202,6 +208,25 @@ fn truncate_invalid_args() {
     suffix.truncate(2, 1);
 }
 
+#[test]
+fn try_append_truncate_errors() {
+    let mut suffix = Suffix::default();
+    assert_eq!(Err(SuffixError::UnsupportedVersion), suffix.try_append(vec![], vec![], 0));
+    assert_eq!(SuffixError::Uninitialized, suffix.try_truncate(1, 2).err().unwrap());
+    assert_eq!(None, suffix.lwm());
+
+    assert_eq!(Ok(AppendResult::Appended), suffix.try_append(vec![], vec![], 3));
+    assert_eq!(Ok(AppendResult::Skipped(Nonmonotonic)), suffix.try_append(vec![], vec![], 2));
+    assert_eq!(
+        SuffixError::InvalidExtents { min_extent: 0, max_extent: 1 },
+        suffix.try_truncate(0, 1).err().unwrap()
+    );
+    let err = suffix.try_truncate(2, 1).err().unwrap();
+    assert_eq!("invalid min_extent (2), max_extent (1)", err.to_string());
+    assert_eq!(Some(3), suffix.lwm());
+    assert_eq!(Some(4), suffix.hwm());
+}
+
 fn collect<I>(opt: Option<I>) -> Option<Vec<TruncatedEntry>> where I: Iterator<Item = TruncatedEntry> {
     opt.map(|it| it.collect())
 }
@@ -409,4 +434,153 @@ fn truncate_three_completed_min_1_max_1() {
     // check leftovers
     assert_eq!(vec![(6, &Some(RetainedEntry::pending(&["r6"], &["w6"])))],
                suffix.enumerate().collect::<Vec<_>>());
-}
\ No newline at end of file
+}
+#[test]
+fn append_truncate_u64_keys() {
+    let mut suffix = Suffix::with_capacity(0);
+    assert_eq!(AppendResult::Appended, suffix.append(vec![1u64], vec![2u64], 1));
+    assert_eq!(AppendResult::Appended, suffix.append(vec![3], vec![4], 2));
+    assert_eq!(Some(&RetainedEntry { readset: vec![1], writeset: vec![2], commutes: vec![], scans: vec![], learning: Learning::Certain, completed: false }), suffix.get(1));
+    assert_eq!(CompleteResult::Completed(1), suffix.complete(1));
+    assert_eq!(vec![TruncatedEntry { ver: 1, readset: vec![1], writeset: vec![2] }],
+               suffix.truncate(1, 1).unwrap().collect::<Vec<_>>());
+    assert_eq!((2..3), suffix.range());
+}
+
+#[test]
+fn checkpoint_restore() {
+    let mut suffix = Suffix::default();
+    assert_eq!(0, Suffix::<String>::restore(&suffix.checkpoint()).unwrap().entries.len());
+
+    assert_eq!(AppendResult::Appended, suffix.append(vec!["x".into()], vec!["y".into()], 3));
+    assert_eq!(AppendResult::Appended, suffix.append(vec![], vec!["z".into()], 4));
+    assert_eq!(AppendResult::Appended, suffix.append(vec!["y".into()], vec![], 6));
+    assert_eq!(AppendResult::Appended, suffix.append(vec![], vec![], 7));
+    assert_eq!(CompleteResult::Completed(3), suffix.complete(3));
+    assert_eq!(CompleteResult::Completed(3), suffix.complete(6));
+    // the learning of entries survives a checkpoint, while gaps have none
+    assert!(suffix.set_learning(4, Learning::Uncertain));
+    assert!(suffix.set_learning(6, Learning::Unlearned));
+    assert!(!suffix.set_learning(5, Learning::Unlearned));
+    assert!(!suffix.set_learning(8, Learning::Unlearned));
+
+    let mut restored: Suffix = Suffix::restore(&suffix.checkpoint()).unwrap();
+    assert_eq!(Learning::Unlearned, restored.get(6).unwrap().learning);
+    assert_eq!(suffix.range(), restored.range());
+    assert_eq!(suffix.highest_completed(), restored.highest_completed());
+    for ver in suffix.range() {
+        assert_eq!(suffix.get(ver), restored.get(ver));
+    }
+
+    for suffix in [&mut suffix, &mut restored] {
+        assert_eq!(CompleteResult::Completed(6), suffix.complete(4));
+        assert_eq!(AppendResult::Skipped(Nonmonotonic), suffix.append(vec![], vec![], 5));
+        assert_eq!(Some(vec![
+            TruncatedEntry { ver: 3, readset: vec!["x".into()], writeset: vec!["y".into()] },
+            TruncatedEntry { ver: 4, readset: vec![], writeset: vec!["z".into()] },
+            TruncatedEntry { ver: 6, readset: vec!["y".into()], writeset: vec![] },
+        ]), collect(suffix.truncate(1, 2)));
+    }
+    assert_eq!(suffix.range(), restored.range());
+}
+
+#[test]
+fn metrics() {
+    let mut suffix = Suffix::default();
+    assert_eq!(SuffixMetrics {
+        lwm: None,
+        hwm: None,
+        highest_completed: None,
+        retained: 0,
+        incomplete: 0,
+        decision_lag: 0,
+        truncation_lag: 0,
+        truncations: 0,
+        truncated: 0,
+    }, suffix.metrics());
+
+    for ver in [3, 5, 6, 8] {
+        assert_eq!(AppendResult::Appended, suffix.append(vec![], vec![], ver));
+    }
+    assert_eq!(CompleteResult::Completed(4), suffix.complete(3));
+    assert_eq!(CompleteResult::Completed(4), suffix.complete(6));
+    assert_eq!(CompleteResult::Completed(4), suffix.complete(6));
+    assert_eq!(SuffixMetrics {
+        lwm: Some(3),
+        hwm: Some(9),
+        highest_completed: Some(4),
+        retained: 4,
+        incomplete: 2,
+        decision_lag: 4,
+        truncation_lag: 2,
+        truncations: 0,
+        truncated: 0,
+    }, suffix.metrics());
+
+    assert_eq!(CompleteResult::Completed(7), suffix.complete(5));
+    assert_eq!(3, collect(suffix.truncate(1, 2)).unwrap().len());
+    assert_eq!(None, collect(suffix.truncate(1, 1)));
+    assert_eq!(SuffixMetrics {
+        lwm: Some(8),
+        hwm: Some(9),
+        highest_completed: Some(7),
+        retained: 1,
+        incomplete: 1,
+        decision_lag: 1,
+        truncation_lag: 0,
+        truncations: 1,
+        truncated: 3,
+    }, suffix.metrics());
+
+    let restored: Suffix = Suffix::restore(&suffix.checkpoint()).unwrap();
+    assert_eq!(SuffixMetrics { truncations: 0, truncated: 0, ..suffix.metrics() }, restored.metrics());
+}
+
+#[test]
+fn retract() {
+    let mut suffix = Suffix::default();
+    assert_eq!(None, suffix.retract(3));
+    assert_eq!(AppendResult::Appended, suffix.append(vec!["x".into()], vec!["y".into()], 3));
+    assert_eq!(AppendResult::Appended, suffix.append(vec![], vec!["z".into()], 5));
+    assert_eq!(None, suffix.retract(4));
+    assert_eq!(Some((vec!["x".into()], vec!["y".into()])), suffix.retract(3));
+
+    // the retracted entry is completed and truncated as any other
+    assert_eq!(CompleteResult::Completed(4), suffix.complete(3));
+    assert_eq!(Some(vec![TruncatedEntry { ver: 3, readset: vec![], writeset: vec![] }]), collect(suffix.truncate(1, 2)));
+    assert_eq!(None, suffix.retract(3));
+}
+
+#[test]
+fn append_record() {
+    let mut suffix = Suffix::default();
+    let rec = Record {
+        xid: Uuid::from_u128(1),
+        readset: vec!["x".into()],
+        scans: vec![Scan::prefix("a")],
+        writeset: vec!["y".into()],
+        commutes: vec!["z".into()],
+        readvers: SortedVec::default(),
+        snapshot: 0,
+    };
+    assert_eq!(AppendResult::Appended, suffix.append_record(rec.clone(), 3));
+    assert_eq!(AppendResult::Appended, suffix.append_record(rec.clone(), 5));
+    assert_eq!(AppendResult::Skipped(Nonmonotonic), suffix.append_record(rec.clone(), 4));
+    let entry = suffix.get(3).unwrap();
+    assert_eq!((vec![Scan::prefix("a")], vec!["z".to_string()]), (entry.scans.clone(), entry.commutes.clone()));
+
+    // the commutative writes and scans survive a checkpoint
+    let mut restored: Suffix = Suffix::restore(&suffix.checkpoint()).unwrap();
+    assert_eq!(suffix.get(3), restored.get(3));
+
+    // retracting or truncating an entry yields its commutative writes among the rest
+    assert_eq!(Some((vec!["x".into()], vec!["y".into(), "z".into()])), restored.retract(5));
+    assert_eq!(Some(&RetainedEntry::pending(&[], &[])), restored.get(5));
+    assert_eq!(None, suffix.last_truncated());
+    assert_eq!(CompleteResult::Completed(4), suffix.complete(3));
+    assert_eq!(Some(vec![TruncatedEntry::new(3, &["x"], &["y", "z"])]), collect(suffix.truncate(1, 1)));
+    assert_eq!(Some(3), suffix.last_truncated());
+    // the gap truncated with it leaves the low-water mark beyond the entry
+    assert_eq!(Some(5), suffix.lwm());
+    assert_eq!(Some(3), Suffix::<String>::restore(&suffix.checkpoint()).unwrap().last_truncated());
+}
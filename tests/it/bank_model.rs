@@ -44,6 +44,9 @@ struct BankCfg<'a> {
     num_cohorts: usize,
     txns_per_cohort: usize,
     extents: &'a [usize],
+    retrying: bool, // whether cohorts hold off retrying until caught up with the refresh hint
+    keyed: bool,    // whether commits carry the safepoint of each change
+    precise: bool,  // whether certifiers leave their assertive aborts unlearned
     name: &'a str,
 }
 
@@ -51,8 +54,21 @@ fn build_model(cfg: BankCfg) -> Model<SystemState> {
     let num_cohorts = cfg.num_cohorts;
     let num_certifiers = cfg.extents.len();
     let values = cfg.values;
-    let mut model = Model::new(move || SystemState::new(num_cohorts, values, num_certifiers))
-        .with_name(cfg.name.into());
+    let (retrying, keyed, precise) = (cfg.retrying, cfg.keyed, cfg.precise);
+    let mut model = Model::new(move || {
+        let mut system = SystemState::new(num_cohorts, values, num_certifiers);
+        if retrying {
+            system = system.with_refresh_hints();
+        }
+        if keyed {
+            system = system.with_key_safepoints(|item| format!("item-{}", item));
+        }
+        if precise {
+            system = system.with_precise_aborts();
+        }
+        system
+    })
+    .with_name(cfg.name.into());
 
     for cohort_index in 0..cfg.num_cohorts {
         let itemset = (0..cfg.values.len())
@@ -60,6 +76,9 @@ fn build_model(cfg: BankCfg) -> Model<SystemState> {
             .collect::<Vec<_>>();
         let txns_per_cohort = cfg.txns_per_cohort;
         model.add_action(format!("initiator-{}", cohort_index), Weak, move |s, c| {
+            if retrying && s.awaiting_retry(cohort_index) {
+                return Blocked;
+            }
             let run = s.cohort_txns(cohort_index);
             let cohort = &mut s.cohorts[cohort_index];
             // list of 'from' accounts that have sufficient funds to initiate a transfer
@@ -93,7 +112,9 @@ fn build_model(cfg: BankCfg) -> Model<SystemState> {
             let xfer_amount = (from_val + 1) / 2;
 
             let readset = vec![itemset[from].clone(), itemset[to].clone()];
-            let writeset = readset.clone();
+            // the writes are listed by item rather than in the order of the changes
+            let mut writeset = readset.clone();
+            writeset.sort();
             let cpt_readvers = vec![from_ver, to_ver];
             let cpt_snapshot = cohort.replica.ver;
             let changes = &[(from, from_val - xfer_amount), (to, to_val + xfer_amount)];
@@ -108,6 +129,7 @@ fn build_model(cfg: BankCfg) -> Model<SystemState> {
                         writeset,
                         readvers,
                         snapshot,
+                        ..Default::default()
                     },
                     statemap,
                 })));
@@ -150,6 +172,9 @@ fn dfs_bank_2x1x1() {
         num_cohorts: 1,
         txns_per_cohort: 1,
         extents: &[1],
+        retrying: false,
+        keyed: false,
+        precise: false,
         name: name_of(&dfs_bank_2x1x1),
     }));
 }
@@ -161,6 +186,9 @@ fn dfs_bank_2x1x2() {
         num_cohorts: 1,
         txns_per_cohort: 2,
         extents: &[2],
+        retrying: false,
+        keyed: false,
+        precise: false,
         name: name_of(&dfs_bank_2x1x2),
     }));
 }
@@ -173,6 +201,9 @@ fn dfs_bank_2x2x1() {
         num_cohorts: 2,
         txns_per_cohort: 1,
         extents: &[2],
+        retrying: false,
+        keyed: false,
+        precise: false,
         name: name_of(&dfs_bank_2x2x1),
     }));
 }
@@ -185,6 +216,9 @@ fn dfs_bank_2x2x2() {
         num_cohorts: 2,
         txns_per_cohort: 2,
         extents: &[4],
+        retrying: false,
+        keyed: false,
+        precise: false,
         name: name_of(&dfs_bank_2x2x2),
     }));
 }
@@ -197,6 +231,9 @@ fn sim_bank_2x1x1() {
             num_cohorts: 1,
             txns_per_cohort: 1,
             extents: &[1],
+            retrying: false,
+            keyed: false,
+            precise: false,
             name: name_of(&sim_bank_2x1x1),
         }),
         10,
@@ -211,6 +248,9 @@ fn sim_bank_2x2x1() {
             num_cohorts: 2,
             txns_per_cohort: 1,
             extents: &[2],
+            retrying: false,
+            keyed: false,
+            precise: false,
             name: name_of(&sim_bank_2x2x1),
         }),
         20,
@@ -225,6 +265,9 @@ fn sim_bank_2x2x2() {
             num_cohorts: 2,
             txns_per_cohort: 2,
             extents: &[4],
+            retrying: false,
+            keyed: false,
+            precise: false,
             name: name_of(&sim_bank_2x2x2),
         }),
         40,
@@ -239,6 +282,9 @@ fn sim_bank_2x3x1() {
             num_cohorts: 3,
             txns_per_cohort: 1,
             extents: &[3],
+            retrying: false,
+            keyed: false,
+            precise: false,
             name: name_of(&sim_bank_2x3x1),
         }),
         40,
@@ -253,6 +299,9 @@ fn sim_bank_2x3x2() {
             num_cohorts: 3,
             txns_per_cohort: 2,
             extents: &[6],
+            retrying: false,
+            keyed: false,
+            precise: false,
             name: name_of(&sim_bank_2x3x2),
         }),
         80,
@@ -267,6 +316,9 @@ fn sim_bank_3x3x2() {
             num_cohorts: 3,
             txns_per_cohort: 2,
             extents: &[6],
+            retrying: false,
+            keyed: false,
+            precise: false,
             name: name_of(&sim_bank_3x3x2),
         }),
         160,
@@ -281,6 +333,9 @@ fn sim_bank_2x4x1() {
             num_cohorts: 4,
             txns_per_cohort: 1,
             extents: &[4],
+            retrying: false,
+            keyed: false,
+            precise: false,
             name: name_of(&sim_bank_2x4x1),
         }),
         80,
@@ -295,6 +350,9 @@ fn sim_bank_2x4x2() {
             num_cohorts: 4,
             txns_per_cohort: 2,
             extents: &[8],
+            retrying: false,
+            keyed: false,
+            precise: false,
             name: name_of(&sim_bank_2x4x2),
         }),
         160,
@@ -309,8 +367,141 @@ fn sim_bank_3x4x2() {
             num_cohorts: 4,
             txns_per_cohort: 2,
             extents: &[8],
+            retrying: false,
+            keyed: false,
+            precise: false,
             name: name_of(&sim_bank_3x4x2),
         }),
         160,
     );
 }
+
+#[test]
+fn dfs_bank_2x1x2_retrying() {
+    dfs(&build_model(BankCfg {
+        values: &[101, 103],
+        num_cohorts: 1,
+        txns_per_cohort: 2,
+        extents: &[2],
+        retrying: true,
+        keyed: false,
+        precise: false,
+        name: name_of(&dfs_bank_2x1x2_retrying),
+    }));
+}
+
+#[test]
+fn sim_bank_2x3x2_retrying() {
+    sim(
+        &build_model(BankCfg {
+            values: &[101, 103],
+            num_cohorts: 3,
+            txns_per_cohort: 2,
+            extents: &[6],
+            retrying: true,
+            keyed: false,
+            precise: false,
+            name: name_of(&sim_bank_2x3x2_retrying),
+        }),
+        80,
+    );
+}
+
+#[test]
+fn sim_bank_3x4x2_retrying() {
+    sim(
+        &build_model(BankCfg {
+            values: &[101, 103, 105],
+            num_cohorts: 4,
+            txns_per_cohort: 2,
+            extents: &[8],
+            retrying: true,
+            keyed: false,
+            precise: false,
+            name: name_of(&sim_bank_3x4x2_retrying),
+        }),
+        160,
+    );
+}
+
+#[test]
+fn dfs_bank_2x1x2_keyed() {
+    dfs(&build_model(BankCfg {
+        values: &[101, 103],
+        num_cohorts: 1,
+        txns_per_cohort: 2,
+        extents: &[2],
+        retrying: false,
+        keyed: true,
+        precise: false,
+        name: name_of(&dfs_bank_2x1x2_keyed),
+    }));
+}
+
+#[test]
+fn sim_bank_2x3x2_keyed() {
+    sim(
+        &build_model(BankCfg {
+            values: &[101, 103],
+            num_cohorts: 3,
+            txns_per_cohort: 2,
+            extents: &[6],
+            retrying: false,
+            keyed: true,
+            precise: false,
+            name: name_of(&sim_bank_2x3x2_keyed),
+        }),
+        80,
+    );
+}
+
+#[test]
+fn sim_bank_3x4x2_keyed() {
+    sim(
+        &build_model(BankCfg {
+            values: &[101, 103, 105],
+            num_cohorts: 4,
+            txns_per_cohort: 2,
+            extents: &[8],
+            retrying: false,
+            keyed: true,
+            precise: false,
+            name: name_of(&sim_bank_3x4x2_keyed),
+        }),
+        160,
+    );
+}
+
+#[test]
+fn sim_bank_2x3x2_precise() {
+    sim(
+        &build_model(BankCfg {
+            values: &[101, 103],
+            num_cohorts: 3,
+            txns_per_cohort: 2,
+            extents: &[6],
+            retrying: false,
+            keyed: false,
+            precise: true,
+            name: name_of(&sim_bank_2x3x2_precise),
+        }),
+        80,
+    );
+}
+
+#[test]
+fn sim_bank_3x4x2_asymmetric_precise() {
+    sim(
+        &build_model(BankCfg {
+            values: &[101, 103, 105],
+            num_cohorts: 4,
+            txns_per_cohort: 2,
+            extents: &[1, 8],
+            retrying: false,
+            keyed: false,
+            precise: true,
+            name: name_of(&sim_bank_3x4x2_asymmetric_precise),
+        }),
+        160,
+    );
+}
@@ -73,6 +73,7 @@ fn build_model(cfg: BlindCfg) -> Model<SystemState> {
                     writeset,
                     readvers,
                     snapshot,
+                    ..Default::default()
                 },
                 statemap,
             })));
@@ -0,0 +1,245 @@
+use std::rc::Rc;
+
+use stride::examiner::Record;
+use stride::havoc::model::{Model, name_of};
+use stride::havoc::model::ActionResult::{Joined, Ran};
+use stride::havoc::model::Retention::{Strong, Weak};
+
+use crate::fixtures::schema::CandidateData;
+use crate::fixtures::schema::MessageKind::CandidateMessage;
+use crate::harness::{dfs, sim};
+use crate::utils::uuidify;
+
+use super::fixtures::*;
+
+fn asserter(values: &[i32], cohort_index: usize) -> impl Fn(&[Cohort]) -> AfterCheck {
+    let expected_sum = values.iter().sum::<i32>();
+    move |_| {
+        Box::new(move |after| {
+            let replica = &after[cohort_index].replica;
+            let computed_sum = replica.items.iter().map(|&(item_val, _)| item_val).sum::<i32>();
+            if expected_sum != computed_sum {
+                Some(format!("expected: {}, computed: {} for {:?}", expected_sum, computed_sum, replica))
+            } else {
+                None
+            }
+        })
+    }
+}
+
+struct CounterCfg<'a> {
+    values: &'a [i32],
+    // each cohort moves a unit from the first counter to the second with a pair of blind increments
+    combos: &'a [(usize, usize)],
+    // an optional cohort that reads the first two counters and overwrites them, folding the second
+    // into the first
+    rebalance: bool,
+    txns_per_cohort: usize,
+    extents: &'a [usize],
+    name: &'a str,
+}
+
+fn build_model(cfg: CounterCfg) -> Model<SystemState> {
+    let num_cohorts = cfg.combos.len() + if cfg.rebalance { 1 } else { 0 };
+    let num_certifiers = cfg.extents.len();
+    let values = cfg.values;
+    let mut model = Model::new(move || SystemState::new(num_cohorts, values, num_certifiers))
+        .with_name(cfg.name.into());
+
+    let txns_per_cohort = cfg.txns_per_cohort;
+    for (cohort_index, &(p, q)) in cfg.combos.iter().enumerate() {
+        let itemset = [format!("item-{}", p), format!("item-{}", q)];
+        model.add_action(format!("initiator-{}", cohort_index), Weak, move |s, _| {
+            let run = s.cohort_txns(cohort_index);
+            let cohort = &mut s.cohorts[cohort_index];
+            let (readvers, snapshot) = Record::compress(vec![], cohort.replica.ver);
+            let statemap = Statemap::commutative(vec![(p, Op::Add(-1)), (q, Op::Add(1))]);
+            cohort
+                .stream
+                .produce(Rc::new(CandidateMessage(CandidateData {
+                    rec: Record {
+                        xid: uuidify(cohort_index, run),
+                        readset: vec![],
+                        writeset: vec![],
+                        commutes: itemset.to_vec(),
+                        readvers,
+                        snapshot,
+                        ..Default::default()
+                    },
+                    statemap,
+                })));
+            if run + 1 == txns_per_cohort {
+                Joined
+            } else {
+                Ran
+            }
+        });
+    }
+    if cfg.rebalance {
+        let cohort_index = cfg.combos.len();
+        let itemset = vec!["item-0".to_string(), "item-1".to_string()];
+        model.add_action(format!("rebalancer-{}", cohort_index), Weak, move |s, _| {
+            let run = s.cohort_txns(cohort_index);
+            let cohort = &mut s.cohorts[cohort_index];
+            let ((old_0_val, old_0_ver), (old_1_val, old_1_ver)) =
+                (cohort.replica.items[0], cohort.replica.items[1]);
+            let cpt_readvers = vec![old_0_ver, old_1_ver];
+            let cpt_snapshot = cohort.replica.ver;
+            let statemap = Statemap::map(&[(0, old_0_val + old_1_val), (1, 0)], Op::Set);
+            let (readvers, snapshot) = Record::compress(cpt_readvers, cpt_snapshot);
+            cohort
+                .stream
+                .produce(Rc::new(CandidateMessage(CandidateData {
+                    rec: Record {
+                        xid: uuidify(cohort_index, run),
+                        readset: itemset.clone(),
+                        writeset: itemset.clone(),
+                        readvers,
+                        snapshot,
+                        ..Default::default()
+                    },
+                    statemap,
+                })));
+            if run + 1 == txns_per_cohort {
+                Joined
+            } else {
+                Ran
+            }
+        });
+    }
+    for cohort_index in 0..num_cohorts {
+        model.add_action(
+            format!("updater-{}", cohort_index),
+            Weak,
+            updater_action(cohort_index, asserter(values, cohort_index)),
+        );
+        model.add_action(
+            format!("replicator-{}", cohort_index),
+            Weak,
+            replicator_action(cohort_index, asserter(values, cohort_index)),
+        );
+    }
+    for (certifier_index, &extent) in cfg.extents.iter().enumerate() {
+        model.add_action(
+            format!("certifier-{}", certifier_index),
+            Weak,
+            certifier_action(certifier_index, extent),
+        );
+    }
+    model.add_action(
+        "supervisor".into(),
+        Strong,
+        supervisor_action(num_cohorts * cfg.txns_per_cohort),
+    );
+    model
+}
+
+#[test]
+fn dfs_counter_1x1() {
+    dfs(&build_model(CounterCfg {
+        values: &[10, 10],
+        combos: &[(0, 1)],
+        rebalance: false,
+        txns_per_cohort: 1,
+        extents: &[1],
+        name: name_of(&dfs_counter_1x1),
+    }));
+}
+
+#[test]
+fn dfs_counter_1x2() {
+    dfs(&build_model(CounterCfg {
+        values: &[10, 10],
+        combos: &[(0, 1)],
+        rebalance: false,
+        txns_per_cohort: 2,
+        extents: &[2],
+        name: name_of(&dfs_counter_1x2),
+    }));
+}
+
+#[test]
+#[ignore]
+fn dfs_counter_2x1() {
+    dfs(&build_model(CounterCfg {
+        values: &[10, 10],
+        combos: &[(0, 1), (1, 0)],
+        rebalance: false,
+        txns_per_cohort: 1,
+        extents: &[2],
+        name: name_of(&dfs_counter_2x1),
+    }));
+}
+
+#[test]
+#[ignore]
+fn dfs_counter_1x1_rebalance() {
+    dfs(&build_model(CounterCfg {
+        values: &[10, 10],
+        combos: &[(0, 1)],
+        rebalance: true,
+        txns_per_cohort: 1,
+        extents: &[2],
+        name: name_of(&dfs_counter_1x1_rebalance),
+    }));
+}
+
+#[test]
+fn sim_counter_2x2() {
+    sim(
+        &build_model(CounterCfg {
+            values: &[10, 10],
+            combos: &[(0, 1), (1, 0)],
+            rebalance: false,
+            txns_per_cohort: 2,
+            extents: &[4],
+            name: name_of(&sim_counter_2x2),
+        }),
+        40,
+    );
+}
+
+#[test]
+fn sim_counter_3x2() {
+    sim(
+        &build_model(CounterCfg {
+            values: &[10, 10, 10],
+            combos: &[(0, 1), (1, 2), (2, 0)],
+            rebalance: false,
+            txns_per_cohort: 2,
+            extents: &[6],
+            name: name_of(&sim_counter_3x2),
+        }),
+        80,
+    );
+}
+
+#[test]
+fn sim_counter_2x2_rebalance() {
+    sim(
+        &build_model(CounterCfg {
+            values: &[10, 10],
+            combos: &[(0, 1), (1, 0)],
+            rebalance: true,
+            txns_per_cohort: 2,
+            extents: &[6],
+            name: name_of(&sim_counter_2x2_rebalance),
+        }),
+        80,
+    );
+}
+
+#[test]
+fn sim_counter_3x2_rebalance_2x8() {
+    sim(
+        &build_model(CounterCfg {
+            values: &[10, 10, 10],
+            combos: &[(0, 1), (1, 2), (2, 0)],
+            rebalance: true,
+            txns_per_cohort: 2,
+            extents: &[8, 8],
+            name: name_of(&sim_counter_3x2_rebalance_2x8),
+        }),
+        160,
+    );
+}
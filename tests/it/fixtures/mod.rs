@@ -2,7 +2,9 @@ use std::fmt::Debug;
 use std::ops::Deref;
 use std::rc::Rc;
 
-use stride::examiner::{Candidate, Examiner, Outcome};
+use rustc_hash::FxHashMap;
+use stride::examiner::{Candidate, Digest, Examiner, Isolation, Outcome};
+use stride::interner::{Interner, Sym};
 use stride::havoc::model::{ActionResult, Context, rand_element};
 use stride::havoc::model::ActionResult::{Blocked, Breached, Joined, Ran};
 use stride::suffix::{Suffix, AppendResult, CompleteResult};
@@ -11,12 +13,13 @@ use crate::fixtures::broker::{Broker, Stream};
 use crate::fixtures::xdb::Redaction::{Existing, New};
 use crate::fixtures::xdb::Xdb;
 use crate::utils::deuuid;
-use crate::fixtures::schema::{MessageKind, DecisionMessageKind, AbortData, CommitData};
+use uuid::Uuid;
+use crate::fixtures::schema::{MessageKind, DecisionMessageKind, AbortData, CommitData, VoteData};
 use crate::fixtures::schema::MessageKind::DecisionMessage;
 
 mod broker;
 pub mod schema;
-mod xdb;
+pub mod xdb;
 
 #[derive(Debug)]
 pub struct SystemState {
@@ -30,21 +33,58 @@ impl SystemState {
         let broker = Broker::new(1);
         let cohorts = (0..num_cohorts)
             .map(|_| Cohort {
-                replica: Replica::new(&init_values),
+                replica: Replica::new(init_values),
                 stream: broker.stream(),
             })
             .collect();
         let certifiers = (0..num_certifiers)
             .map(|_| Certifier {
-                suffix: Suffix::default(),
+                interner: Interner::default(),
+                suffix: Suffix::with_capacity(0),
                 examiner: Examiner::default(),
                 stream: broker.stream(),
+                extent: 0,
+                digests: vec![],
+                key_safepoints: None,
             })
             .collect();
 
         SystemState { cohorts, certifiers, xdb: Xdb::default() }
     }
 
+    pub fn with_isolation(mut self, isolation: Isolation) -> Self {
+        for certifier in &mut self.certifiers {
+            certifier.examiner = std::mem::take(&mut certifier.examiner).with_isolation(isolation);
+        }
+        self
+    }
+
+    pub fn with_refresh_hints(mut self) -> Self {
+        for certifier in &mut self.certifiers {
+            certifier.examiner = std::mem::take(&mut certifier.examiner).with_refresh_hints();
+        }
+        self
+    }
+
+    // Has the certifiers leave their assertive aborts unlearned; they must all agree on the mode,
+    // though not on their extents.
+    pub fn with_precise_aborts(mut self) -> Self {
+        for certifier in &mut self.certifiers {
+            certifier.examiner = std::mem::take(&mut certifier.examiner).with_precise_aborts();
+        }
+        self
+    }
+
+    // Has commits carry the safepoint of each change, so that replicas install each change out of
+    // order once they reach its own safepoint. The certifiers find the write of each change by the
+    // key of its item, as the model names it.
+    pub fn with_key_safepoints(mut self, item_key: fn(usize) -> String) -> Self {
+        for certifier in &mut self.certifiers {
+            certifier.key_safepoints = Some(item_key);
+        }
+        self
+    }
+
     pub fn total_txns(&self) -> usize {
         self.certifiers[0]
             .stream
@@ -60,6 +100,30 @@ impl SystemState {
             MessageKind::DecisionMessage(_) => false,
         })
     }
+
+    // Whether the cohort should hold off retrying: until its last transaction is decided, and if
+    // aborted, until its replica catches up with the refresh hint. Retrying from an older snapshot
+    // is bound to abort again.
+    pub fn awaiting_retry(&self, cohort_index: usize) -> bool {
+        let run = self.cohort_txns(cohort_index);
+        if run == 0 {
+            return false;
+        }
+        let decisions = self.certifiers[0].stream.find(|msg| match msg {
+            MessageKind::CandidateMessage(_) => false,
+            MessageKind::DecisionMessage(decision) => {
+                deuuid::<usize, usize>(decision.candidate().rec.xid) == (cohort_index, run - 1)
+            }
+        });
+        let replica = &self.cohorts[cohort_index].replica;
+        match decisions.first().and_then(|(_, msg)| msg.as_decision()) {
+            None => true,
+            Some(DecisionMessageKind::AbortMessage(AbortData { refresh: Some(refresh), .. })) => {
+                replica.ver < refresh.snapshot
+            }
+            Some(_) => false,
+        }
+    }
 }
 
 impl CertifierState for SystemState {
@@ -80,6 +144,77 @@ impl XdbState for SystemState {
     }
 }
 
+// A system whose items are split across partitions, item i belonging to partition i % P. Each
+// partition has its own log and certifiers, and each cohort a replica of every partition. A
+// cohort submits a slice of its transaction to every partition's log, even if empty, so that the
+// transaction's outcome is decided in every log; the partitions' certifiers vote on their slices,
+// and the combiner decides the outcome from the first vote of each partition.
+#[derive(Debug)]
+pub struct PartitionedState {
+    pub partitions: Vec<Vec<Certifier>>,
+    pub cohorts: Vec<Cohort>, // a replica of each partition per cohort, partitions varying fastest
+    pub votes: Stream<VoteData<Statemap>>,
+    pub ballots: FxHashMap<Uuid, Vec<Option<Rc<VoteData<Statemap>>>>>, // each partition's vote, once cast
+    pub xdb: Xdb,
+}
+
+impl PartitionedState {
+    pub fn new(num_partitions: usize, num_cohorts: usize, init_values: &[i32], num_certifiers: usize) -> Self {
+        let brokers = (0..num_partitions).map(|_| Broker::new(1)).collect::<Vec<_>>();
+        let cohorts = (0..num_cohorts)
+            .flat_map(|_| brokers.iter())
+            .map(|broker| Cohort {
+                replica: Replica::new(init_values),
+                stream: broker.stream(),
+            })
+            .collect();
+        let partitions = brokers
+            .iter()
+            .map(|broker| {
+                (0..num_certifiers)
+                    .map(|_| Certifier {
+                        interner: Interner::default(),
+                        suffix: Suffix::with_capacity(0),
+                        examiner: Examiner::default(),
+                        stream: broker.stream(),
+                        extent: 0,
+                        digests: vec![],
+                        key_safepoints: None,
+                    })
+                    .collect()
+            })
+            .collect();
+
+        PartitionedState {
+            partitions,
+            cohorts,
+            votes: Broker::new(1).stream(),
+            ballots: FxHashMap::default(),
+            xdb: Xdb::default(),
+        }
+    }
+
+    pub fn num_partitions(&self) -> usize {
+        self.partitions.len()
+    }
+
+    pub fn cohort_txns(&self, cohort_index: usize) -> usize {
+        self.partitions[0][0].stream.count(|msg| match msg {
+            MessageKind::CandidateMessage(candidate) => {
+                let (pid, _) = deuuid::<usize, usize>(candidate.rec.xid);
+                pid == cohort_index
+            }
+            MessageKind::DecisionMessage(_) => false,
+        })
+    }
+}
+
+impl CohortState for PartitionedState {
+    fn cohorts(&mut self) -> &mut [Cohort] {
+        &mut self.cohorts
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum Op {
     Set(i32),
@@ -100,11 +235,27 @@ impl Op {
 #[derive(Debug, Clone)]
 pub struct Statemap {
     pub changes: Vec<(usize, Op)>,
+    commutative: Vec<bool>, // whether each change commutes with the others of its item
 }
 
 impl Statemap {
     pub fn new(changes: Vec<(usize, Op)>) -> Self {
-        Statemap { changes }
+        let commutative = vec![false; changes.len()];
+        Statemap { changes, commutative }
+    }
+
+    // changes certified as commutative writes, e.g., increments, which combine with the others of
+    // their items rather than overwriting them
+    pub fn commutative(changes: Vec<(usize, Op)>) -> Self {
+        let commutative = vec![true; changes.len()];
+        Statemap { changes, commutative }
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (usize, Op, bool)> + '_ {
+        self.changes
+            .iter()
+            .zip(&self.commutative)
+            .map(|(&(change_item, change_value), &commutative)| (change_item, change_value, commutative))
     }
 
     pub fn map<M>(changes: &[(usize, i32)], mapper: M) -> Self
@@ -128,15 +279,20 @@ pub struct Cohort {
 
 #[derive(Debug)]
 pub struct Certifier {
-    pub suffix: Suffix,
-    pub examiner: Examiner,
+    pub interner: Interner<String>,
+    pub suffix: Suffix<Sym>,
+    pub examiner: Examiner<Sym>,
     pub stream: Stream<MessageKind<Statemap>>,
+    pub extent: usize,
+    pub digests: Vec<Digest>, // the examiner's digest after each assessment
+    pub key_safepoints: Option<fn(usize) -> String>, // the key of an item, if commits carry the safepoint of each change
 }
 
 #[derive(Debug)]
 pub struct Replica {
     pub items: Vec<(i32, u64)>,
     pub ver: u64,
+    commuted: Vec<(usize, u64)>, // commutative changes installed ahead of the replica's version
 }
 
 impl Replica {
@@ -144,14 +300,22 @@ impl Replica {
         Replica {
             items: values.iter().map(|&i| (i, 0)).collect(),
             ver: 0,
+            commuted: vec![],
+        }
+    }
+
+    // a commutative change may be installed ahead of earlier changes to its item, but only once
+    fn is_installed(&self, change_item: usize, commutative: bool, ver: u64) -> bool {
+        match commutative {
+            false => ver <= self.items[change_item].1,
+            true => self.commuted.contains(&(change_item, ver)),
         }
     }
 
     pub fn can_install_ooo(&self, statemap: &Statemap, safepoint: u64, ver: u64) -> bool {
         if self.ver >= safepoint && ver > self.ver {
-            for &(change_item, _) in &statemap.changes {
-                let &(_, existing_ver) = &self.items[change_item];
-                if ver > existing_ver {
+            for (change_item, _, commutative) in statemap.iter() {
+                if !self.is_installed(change_item, commutative, ver) {
                     return true;
                 }
             }
@@ -160,12 +324,20 @@ impl Replica {
     }
 
     fn install_items(&mut self, statemap: &Statemap, ver: u64) {
-        for (change_item, change_value) in &statemap.changes {
-            let existing = &mut self.items[*change_item];
-            if ver > existing.1 {
-                *existing = (change_value.eval(existing.0), ver);
+        for (change_item, change_value, commutative) in statemap.iter() {
+            if !self.is_installed(change_item, commutative, ver) {
+                if commutative {
+                    self.commuted.push((change_item, ver));
+                }
+                // a commutative change leaves the item's version alone, as earlier changes to the
+                // item may yet be uninstalled
+                let existing = &mut self.items[change_item];
+                let item_ver = if commutative { existing.1 } else { std::cmp::max(existing.1, ver) };
+                *existing = (change_value.eval(existing.0), item_ver);
             }
         }
+        let replica_ver = self.ver;
+        self.commuted.retain(|&(_, commuted_ver)| commuted_ver > replica_ver);
     }
 
     pub fn install_ooo(&mut self, statemap: &Statemap, safepoint: u64, ver: u64) {
@@ -174,6 +346,35 @@ impl Replica {
         }
     }
 
+    // as can_install_ooo(), but with each change behind its own safepoint, so that some changes of
+    // a transaction may be installed ahead of the others
+    pub fn can_install_keyed(&self, statemap: &Statemap, safepoints: &[u64], ver: u64) -> bool {
+        ver > self.ver
+            && statemap
+                .iter()
+                .zip(safepoints)
+                .any(|((change_item, _, commutative), &safepoint)| {
+                    self.ver >= safepoint && !self.is_installed(change_item, commutative, ver)
+                })
+    }
+
+    pub fn install_keyed(&mut self, statemap: &Statemap, safepoints: &[u64], ver: u64) {
+        if ver > self.ver {
+            let (changes, commutative) = statemap
+                .iter()
+                .zip(safepoints)
+                .filter(|&(_, &safepoint)| self.ver >= safepoint)
+                .map(|((change_item, change_value, commutative), _)| ((change_item, change_value), commutative))
+                .unzip();
+            self.install_items(&Statemap { changes, commutative }, ver);
+        }
+    }
+
+    // advances past an aborted transaction, which leaves the items unchanged
+    pub fn skip(&mut self, ver: u64) {
+        self.install_ser(&Statemap::new(vec![]), ver);
+    }
+
     pub fn install_ser(&mut self, statemap: &Statemap, ver: u64) {
         if ver > self.ver {
             self.install_items(statemap, ver);
@@ -194,13 +395,16 @@ pub trait XdbState {
     fn xdb(&mut self) -> &mut Xdb;
 }
 
+// the check that an asserter derives from the cohorts before an update, to be made of them after it
+pub type AfterCheck = Box<dyn Fn(&[Cohort]) -> Option<String>>;
+
 pub fn updater_action<S, A>(
     cohort_index: usize,
     asserter: A,
 ) -> impl Fn(&mut S, &mut dyn Context) -> ActionResult
 where
     S: CohortState,
-    A: Fn(&[Cohort]) -> Box<dyn Fn(&[Cohort]) -> Option<String>>,
+    A: Fn(&[Cohort]) -> AfterCheck,
 {
     move |s, c| {
         let installable_commits = {
@@ -208,11 +412,12 @@ where
             cohort.stream.find(|message| match message {
                 MessageKind::CandidateMessage(_) => false,
                 MessageKind::DecisionMessage(decision) => match decision {
-                    DecisionMessageKind::CommitMessage(commit) => cohort.replica.can_install_ooo(
-                        &commit.statemap,
-                        commit.safepoint,
-                        commit.candidate.ver,
-                    ),
+                    DecisionMessageKind::CommitMessage(commit) => match &commit.key_safepoints {
+                        Some(key_safepoints) => {
+                            cohort.replica.can_install_keyed(&commit.statemap, key_safepoints, commit.candidate.ver)
+                        }
+                        None => cohort.replica.can_install_ooo(&commit.statemap, commit.safepoint, commit.candidate.ver),
+                    },
                     DecisionMessageKind::AbortMessage(_) => false,
                 },
             })
@@ -221,13 +426,16 @@ where
         if !installable_commits.is_empty() {
             log::trace!("Installable {:?}", installable_commits);
             let (_, commit) = rand_element(c, &installable_commits);
-            let after_check = asserter(&s.cohorts());
+            let after_check = asserter(s.cohorts());
             let cohort = &mut s.cohorts()[cohort_index];
             let commit = commit.as_decision().unwrap().as_commit().unwrap();
-            cohort
-                .replica
-                .install_ooo(&commit.statemap, commit.safepoint, commit.candidate.ver);
-            if let Some(error) = after_check(&s.cohorts()) {
+            match &commit.key_safepoints {
+                Some(key_safepoints) => {
+                    cohort.replica.install_keyed(&commit.statemap, key_safepoints, commit.candidate.ver)
+                }
+                None => cohort.replica.install_ooo(&commit.statemap, commit.safepoint, commit.candidate.ver),
+            }
+            if let Some(error) = after_check(s.cohorts()) {
                 return Breached(error);
             }
             Ran
@@ -243,7 +451,7 @@ pub fn replicator_action<S, A>(
 ) -> impl Fn(&mut S, &mut dyn Context) -> ActionResult
 where
     S: CohortState,
-    A: Fn(&[Cohort]) -> Box<dyn Fn(&[Cohort]) -> Option<String>>,
+    A: Fn(&[Cohort]) -> AfterCheck,
 {
     move |s, _| {
         let mut at_least_one_decision_consumed = false;
@@ -265,16 +473,19 @@ where
                             at_least_one_decision_consumed = true;
                             match decision {
                                 DecisionMessageKind::CommitMessage(commit) => {
-                                    let after_check = asserter(&s.cohorts());
+                                    let after_check = asserter(s.cohorts());
                                     let cohort = &mut s.cohorts()[cohort_index];
                                     cohort
                                         .replica
                                         .install_ser(&commit.statemap, commit.candidate.ver);
-                                    if let Some(error) = after_check(&s.cohorts()) {
+                                    if let Some(error) = after_check(s.cohorts()) {
                                         return Breached(error);
                                     }
                                 }
-                                DecisionMessageKind::AbortMessage(_) => {}
+                                DecisionMessageKind::AbortMessage(abort) => {
+                                    let cohort = &mut s.cohorts()[cohort_index];
+                                    cohort.replica.skip(abort.candidate.ver);
+                                }
                             }
                         }
                     }
@@ -299,27 +510,67 @@ where
                 match message.deref() {
                     MessageKind::CandidateMessage(candidate_message) => {
                         let certifier = &mut s.certifiers()[certifier_index];
-                        let result = certifier.suffix.append(
-                            candidate_message.rec.readset.clone(),
-                            candidate_message.rec.writeset.clone(),
-                            offset as u64,
-                        );
+                        let interned = match certifier.interner.try_intern_record(&candidate_message.rec) {
+                            Ok(interned) => interned,
+                            Err(err) => return Breached(format!("record not interned: {}", err)),
+                        };
+                        let ver = offset as u64;
+                        let (outcome, key_safepoints) = if let Some(item_key) = certifier.key_safepoints {
+                            let (outcome, key_safepoints) = certifier.examiner.assess_keyed(&interned, ver);
+                            log::trace!("Certified {:?} at {} with {:?} by {:?}", &candidate_message.rec, ver, outcome, key_safepoints);
+                            // the safepoints follow the record's writes, so look up each change's
+                            // by the key of its item
+                            let rec = &candidate_message.rec;
+                            let writes = rec.writeset.iter().chain(&rec.commutes).zip(key_safepoints).collect::<Vec<_>>();
+                            let mut change_safepoints = vec![];
+                            if outcome.is_commit() {
+                                for &(change_item, _) in &candidate_message.statemap.changes {
+                                    let key = item_key(change_item);
+                                    match writes.iter().find(|&&(write, _)| *write == key) {
+                                        Some(&(_, safepoint)) => change_safepoints.push(safepoint),
+                                        None => return Breached(format!("change of {} has no write: {:?}", key, candidate_message)),
+                                    }
+                                }
+                            }
+                            (outcome, Some(change_safepoints))
+                        } else if log::log_enabled!(log::Level::Trace) {
+                            let candidate = Candidate { rec: interned.clone(), ver };
+                            let (outcome, explanation) = certifier.examiner.assess_explained(candidate);
+                            let interner = &certifier.interner;
+                            log::trace!(
+                                "Certified {:?} at {} {:?} with {:?} by {:?}",
+                                &candidate_message.rec,
+                                ver,
+                                &candidate_message.statemap,
+                                outcome,
+                                explanation.map(|&sym| interner.resolve(sym).unwrap().clone())
+                            );
+                            (outcome, None)
+                        } else {
+                            (certifier.examiner.assess_record(&interned, ver), None)
+                        };
+
+                        // the examiner only borrowed the interned record, so the suffix can own it
+                        let result = certifier.suffix.append_record(interned, ver);
                         if let AppendResult::Skipped(reason) = result {
                             return Breached(format!("suffix append skipped: {:?}", reason));
                         }
+                        // so that an examiner rebuilt from the suffix learns it alike in precise mode
+                        certifier.suffix.set_learning(ver, certifier.examiner.learning(ver));
+                        certifier.extent = extent;
+                        certifier.digests.push(certifier.examiner.digest());
+
+                        // certifiers of like extent replicate one another, so must agree even on
+                        // the permissive outcomes that Xdb tolerates
+                        let certifiers = &s.certifiers()[..];
+                        let ours = &certifiers[certifier_index];
+                        for theirs in certifiers.iter().filter(|theirs| theirs.extent == extent) {
+                            if let Some(ver) = Digest::first_divergence(&ours.digests, &theirs.digests) {
+                                return Breached(format!("certifiers diverged at ver {}", ver));
+                            }
+                        }
 
-                        let candidate = Candidate {
-                            rec: candidate_message.rec.clone(),
-                            ver: offset as u64,
-                        };
-                        let outcome = certifier.examiner.assess(candidate.clone());
-                        log::trace!(
-                            "Certified {:?} {:?} with {:?}",
-                            candidate,
-                            &candidate_message.statemap,
-                            outcome
-                        );
-                        let result = s.xdb().assign(candidate.rec.xid, &outcome);
+                        let result = s.xdb().assign(candidate_message.rec.xid, &outcome);
                         let new_redaction = match result {
                             Ok(New(_)) => {
                                 true
@@ -334,16 +585,21 @@ where
                         };
 
                         if new_redaction {
+                            let candidate = Candidate {
+                                rec: candidate_message.rec.clone(),
+                                ver,
+                            };
                             let decision_message = match outcome {
                                 Outcome::Commit {safepoint, discord: _} => {
                                     DecisionMessageKind::CommitMessage(CommitData {
                                         candidate,
                                         safepoint,
                                         statemap: candidate_message.statemap.clone(),
+                                        key_safepoints,
                                     })
                                 }
-                                Outcome::Abort {reason, discord: _} => {
-                                    DecisionMessageKind::AbortMessage(AbortData { candidate, reason })
+                                Outcome::Abort {reason, discord: _, refresh} => {
+                                    DecisionMessageKind::AbortMessage(AbortData { candidate, reason, refresh })
                                 }
                             };
 
@@ -361,29 +617,178 @@ where
                             return Breached(format!("suffix complete skipped: {:?}", reason));
                         }
 
-                        if {
-                            let truncated = certifier.suffix.truncate(extent, extent);
-                            match truncated {
-                                None => false,
-                                Some(truncated_entries) => {
-                                    for truncated_entry in truncated_entries {
-                                        log::trace!("  truncating {:?}", truncated_entry);
-                                        certifier.examiner.discard(truncated_entry);
-                                    }
-                                    true
+                        let truncated = match certifier.suffix.truncate(extent, extent) {
+                            None => false,
+                            Some(truncated_entries) => {
+                                for truncated_entry in truncated_entries {
+                                    log::trace!("  truncating {:?}", truncated_entry);
+                                    certifier.examiner.discard_interned(truncated_entry, &mut certifier.interner);
                                 }
+                                true
                             }
-                        } {
+                        };
+                        if truncated {
                             log::trace!("    range {:?}", certifier.suffix.range());
                         }
                     }
                 }
+
+                let certifier = &s.certifiers()[certifier_index];
+                let discrepancies = certifier.examiner.validate(&certifier.suffix);
+                if !discrepancies.is_empty() {
+                    return Breached(format!("examiner disagrees with suffix: {:?}", discrepancies));
+                }
                 Ran
             }
         }
     }
 }
 
+// Certifies a partition's log as certifier_action() does a single log, except that the certifier
+// votes on each candidate, casting its vote to the combiner, and settles the vote once the
+// combiner's decision reaches the log. A slice whose transaction aborted is rolled back, both in
+// the examiner and in the suffix.
+pub fn voter_action(
+    partition_index: usize,
+    certifier_index: usize,
+    extent: usize,
+) -> impl Fn(&mut PartitionedState, &mut dyn Context) -> ActionResult {
+    move |s, _| {
+        // looping lets us consume every message available at once, reducing the model diameter
+        let mut consumed = false;
+        while let Some((offset, message)) = s.partitions[partition_index][certifier_index].stream.consume() {
+            consumed = true;
+            let certifier = &mut s.partitions[partition_index][certifier_index];
+            let ver = offset as u64;
+            match message.deref() {
+                MessageKind::CandidateMessage(candidate_message) => {
+                    let interned = match certifier.interner.try_intern_record(&candidate_message.rec) {
+                        Ok(interned) => interned,
+                        Err(err) => return Breached(format!("record not interned: {}", err)),
+                    };
+                    let outcome = certifier.examiner.vote(&interned, ver);
+                    log::trace!("Voted on {:?} at {} in partition {} with {:?}", &candidate_message.rec, ver, partition_index, outcome);
+
+                    let result = certifier.suffix.append_record(interned, ver);
+                    if let AppendResult::Skipped(reason) = result {
+                        return Breached(format!("suffix append skipped: {:?}", reason));
+                    }
+                    certifier.extent = extent;
+                    certifier.digests.push(certifier.examiner.digest());
+
+                    let certifiers = &s.partitions[partition_index];
+                    let ours = &certifiers[certifier_index];
+                    for theirs in certifiers.iter().filter(|theirs| theirs.extent == extent) {
+                        if let Some(ver) = Digest::first_divergence(&ours.digests, &theirs.digests) {
+                            return Breached(format!("certifiers of partition {} diverged at ver {}", partition_index, ver));
+                        }
+                    }
+
+                    s.votes.produce(Rc::new(VoteData {
+                        partition: partition_index,
+                        candidate: Candidate { rec: candidate_message.rec.clone(), ver },
+                        statemap: candidate_message.statemap.clone(),
+                        outcome,
+                    }));
+                }
+                MessageKind::DecisionMessage(decision) => {
+                    let ver = decision.candidate().ver;
+                    let committed = decision.as_commit().is_some();
+                    if let Err(error) = certifier.examiner.try_resolve(ver, committed) {
+                        return Breached(format!("resolve failed: {}", error));
+                    }
+                    if !committed {
+                        if let Some((readset, writeset)) = certifier.suffix.retract(ver) {
+                            certifier.interner.release_all(&readset);
+                            certifier.interner.release_all(&writeset);
+                        }
+                    }
+                    let result = certifier.suffix.complete(ver);
+                    if let CompleteResult::Skipped(reason) = result {
+                        return Breached(format!("suffix complete skipped: {:?}", reason));
+                    }
+                    if let Some(truncated_entries) = certifier.suffix.truncate(extent, extent) {
+                        for truncated_entry in truncated_entries {
+                            certifier.examiner.discard_interned(truncated_entry, &mut certifier.interner);
+                        }
+                    }
+                }
+            }
+
+            let certifier = &s.partitions[partition_index][certifier_index];
+            let discrepancies = certifier.examiner.validate(&certifier.suffix);
+            if !discrepancies.is_empty() {
+                return Breached(format!("examiner disagrees with suffix: {:?}", discrepancies));
+            }
+        }
+        if consumed {
+            Ran
+        } else {
+            Blocked
+        }
+    }
+}
+
+// Gathers the votes on each transaction, deciding its outcome once every partition has voted. The
+// first vote of each partition counts; certifiers of like extent cast the same vote, while those
+// of unlike extent may differ only in the permissive votes that Xdb tolerates. The decision in
+// each partition's log installs the partition's slice behind that partition's own safepoint.
+pub fn combiner_action() -> impl Fn(&mut PartitionedState, &mut dyn Context) -> ActionResult {
+    move |s, _| {
+        // looping lets us consume every vote available at once, reducing the model diameter
+        let mut consumed = false;
+        while let Some((_, vote)) = s.votes.consume() {
+            consumed = true;
+            let num_partitions = s.num_partitions();
+            let ballot = s
+                .ballots
+                .entry(vote.candidate.rec.xid)
+                .or_insert_with(|| vec![None; num_partitions]);
+            let partition = vote.partition;
+            if ballot[partition].is_some() {
+                continue;
+            }
+            ballot[partition] = Some(vote);
+            if ballot.iter().any(Option::is_none) {
+                continue;
+            }
+
+            let votes = ballot.iter().map(|vote| vote.as_ref().unwrap()).collect::<Vec<_>>();
+            let outcome = Outcome::combine(&votes.iter().map(|vote| vote.outcome.clone()).collect::<Vec<_>>());
+            log::trace!("Combined {:?} into {:?}", votes, outcome);
+            if let Err(error) = s.xdb.assign(votes[0].candidate.rec.xid, &outcome) {
+                return Breached(format!("XDB assignment error: {:?}", error));
+            }
+
+            for vote in votes {
+                let candidate = vote.candidate.clone();
+                let decision_message = match (&outcome, &vote.outcome) {
+                    (Outcome::Commit { .. }, Outcome::Commit { safepoint, .. }) => {
+                        DecisionMessageKind::CommitMessage(CommitData {
+                            candidate,
+                            safepoint: *safepoint,
+                            statemap: vote.statemap.clone(),
+                            key_safepoints: None,
+                        })
+                    }
+                    (Outcome::Abort { reason, .. }, _) => DecisionMessageKind::AbortMessage(AbortData {
+                        candidate,
+                        reason: reason.clone(),
+                        refresh: None,
+                    }),
+                    (Outcome::Commit { .. }, Outcome::Abort { .. }) => unreachable!("committed over an abort vote"),
+                };
+                s.partitions[vote.partition][0].stream.produce(Rc::new(DecisionMessage(decision_message)));
+            }
+        }
+        if consumed {
+            Ran
+        } else {
+            Blocked
+        }
+    }
+}
+
 pub fn supervisor_action<S>(
     expected_txns: usize,
 ) -> impl Fn(&mut S, &mut dyn Context) -> ActionResult
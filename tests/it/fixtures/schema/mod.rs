@@ -1,4 +1,4 @@
-use stride::examiner::{Candidate, AbortReason, Record};
+use stride::examiner::{Candidate, AbortReason, Outcome, Record, Refresh};
 
 #[derive(Debug)]
 pub enum MessageKind<S> {
@@ -54,11 +54,22 @@ impl<S> DecisionMessageKind<S> {
 pub struct CommitData<S> {
     pub candidate: Candidate,
     pub safepoint: u64,
-    pub statemap: S
+    pub statemap: S,
+    pub key_safepoints: Option<Vec<u64>>, // the safepoint of each change in the statemap, if reported per key
 }
 
 #[derive(Debug)]
 pub struct AbortData {
     pub candidate: Candidate,
-    pub reason: AbortReason
+    pub reason: AbortReason,
+    pub refresh: Option<Refresh>
+}
+
+// a partition's vote on its slice of a transaction spanning partitions
+#[derive(Debug)]
+pub struct VoteData<S> {
+    pub partition: usize,
+    pub candidate: Candidate,
+    pub statemap: S,
+    pub outcome: Outcome,
 }
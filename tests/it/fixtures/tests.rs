@@ -5,6 +5,7 @@ fn replica_install_items() {
     let mut replica = Replica {
         items: vec![(10, 5), (20, 5), (30, 5)],
         ver: 5,
+        commuted: vec![],
     };
 
     // empty statemap at a newer version -- no change expected
@@ -28,6 +29,7 @@ fn replica_install_ooo() {
     let mut replica = Replica {
         items: vec![(10, 5), (20, 5), (30, 5)],
         ver: 5,
+        commuted: vec![],
     };
 
     // non-empty statemap at the same safepoint and same version -- no change expected
@@ -54,6 +56,7 @@ fn replica_install_ser() {
     let mut replica = Replica {
         items: vec![(10, 5), (20, 5), (30, 5)],
         ver: 5,
+        commuted: vec![],
     };
 
     // non-empty statemap at the same version -- no change expected
@@ -65,4 +68,60 @@ fn replica_install_ser() {
     replica.install_ser(&Statemap::map(&[(0, 11)], Op::Set), 6);
     assert_eq!(vec![(11, 6), (20, 5), (30, 5)], replica.items);
     assert_eq!(6, replica.ver);
-}
\ No newline at end of file
+}
+
+#[test]
+fn replica_install_commuted() {
+    let mut replica = Replica {
+        items: vec![(10, 5), (20, 5)],
+        ver: 5,
+        commuted: vec![],
+    };
+
+    let add = |addend| Statemap::commutative(vec![(0, Op::Add(addend))]);
+
+    // commutative changes install in either order, leaving the version of their item alone
+    assert!(replica.can_install_ooo(&add(2), 5, 7));
+    replica.install_ooo(&add(2), 5, 7);
+    assert!(replica.can_install_ooo(&add(3), 5, 6));
+    replica.install_ooo(&add(3), 5, 6);
+    assert_eq!(vec![(15, 5), (20, 5)], replica.items);
+
+    // but only once
+    assert!(!replica.can_install_ooo(&add(3), 5, 6));
+    replica.install_ser(&add(3), 6);
+    replica.install_ser(&add(2), 7);
+    assert_eq!(vec![(15, 5), (20, 5)], replica.items);
+    assert_eq!(7, replica.ver);
+
+    // whereas an increment that is not commutative overwrites its item, and so is superseded by a
+    // later version
+    assert!(!replica.can_install_ooo(&Statemap::map(&[(0, 4)], Op::Add), 7, 7));
+    replica.install_ooo(&Statemap::map(&[(1, 4)], Op::Add), 7, 8);
+    replica.install_ser(&Statemap::map(&[(1, 5)], Op::Add), 8);
+    assert_eq!(vec![(15, 5), (24, 8)], replica.items);
+}
+
+#[test]
+fn replica_install_keyed() {
+    let mut replica = Replica {
+        items: vec![(10, 5), (20, 5), (30, 5)],
+        ver: 5,
+        commuted: vec![],
+    };
+
+    // only the change whose safepoint the replica reached is installed
+    let statemap = Statemap::map(&[(0, 11), (1, 21)], Op::Set);
+    assert!(replica.can_install_keyed(&statemap, &[5, 6], 7));
+    replica.install_keyed(&statemap, &[5, 6], 7);
+    assert_eq!(vec![(11, 7), (20, 5), (30, 5)], replica.items);
+    assert!(!replica.can_install_keyed(&statemap, &[5, 6], 7));
+
+    // the other follows once the replica reaches its safepoint
+    replica.install_ser(&Statemap::map(&[(2, 31)], Op::Set), 6);
+    assert!(replica.can_install_keyed(&statemap, &[5, 6], 7));
+    replica.install_keyed(&statemap, &[5, 6], 7);
+    assert_eq!(vec![(11, 7), (21, 7), (31, 6)], replica.items);
+    assert!(!replica.can_install_keyed(&statemap, &[5, 6], 7));
+    assert_eq!(6, replica.ver);
+}
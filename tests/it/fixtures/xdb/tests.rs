@@ -21,8 +21,8 @@ fn xdb_assign_assertive_over_assertive_with_conflict() {
     let mut xdb = Xdb::new();
     assert_eq!(Ok(New(&Commit {safepoint: 7, discord: Assertive})),
                xdb.assign(Uuid::nil(), &Commit {safepoint: 7, discord: Assertive}));
-    assert_eq!(Err(Conflict { existing: &Commit {safepoint: 7, discord: Assertive}, new: &Abort {reason: Staleness, discord: Assertive}}),
-               xdb.assign(Uuid::nil(), &Abort {reason: Staleness, discord: Assertive}));
+    assert_eq!(Err(Conflict { existing: &Commit {safepoint: 7, discord: Assertive}, new: &Abort {reason: Staleness, discord: Assertive, refresh: None}}),
+               xdb.assign(Uuid::nil(), &Abort {reason: Staleness, discord: Assertive, refresh: None}));
     assert_eq!(Some(&Commit {safepoint: 7, discord: Assertive}), xdb.get(&Uuid::nil()));
 }
 
@@ -42,8 +42,8 @@ fn xdb_assign_assertive_over_permissive_with_conflict() {
     let mut xdb = Xdb::new();
     assert_eq!(Ok(New(&Commit {safepoint: 7, discord: Permissive})),
                xdb.assign(Uuid::nil(), &Commit {safepoint: 7, discord: Permissive}));
-    assert_eq!(Err(Conflict { existing: &Commit {safepoint: 7, discord: Permissive}, new: &Abort {reason: Staleness, discord: Assertive}}),
-               xdb.assign(Uuid::nil(), &Abort {reason: Staleness, discord: Assertive}));
+    assert_eq!(Err(Conflict { existing: &Commit {safepoint: 7, discord: Permissive}, new: &Abort {reason: Staleness, discord: Assertive, refresh: None}}),
+               xdb.assign(Uuid::nil(), &Abort {reason: Staleness, discord: Assertive, refresh: None}));
     assert_eq!(Some(&Commit {safepoint: 7, discord: Permissive}), xdb.get(&Uuid::nil()));
 }
 
@@ -62,8 +62,8 @@ fn xdb_assign_permissive_over_assertive_with_conflict() {
     let mut xdb = Xdb::new();
     assert_eq!(Ok(New(&Commit {safepoint: 7, discord: Assertive})),
                xdb.assign(Uuid::nil(), &Commit {safepoint: 7, discord: Assertive}));
-    assert_eq!(Err(Conflict { existing: &Commit {safepoint: 7, discord: Assertive}, new: &Abort {reason: Staleness, discord: Permissive}}),
-               xdb.assign(Uuid::nil(), &Abort {reason: Staleness, discord: Permissive}));
+    assert_eq!(Err(Conflict { existing: &Commit {safepoint: 7, discord: Assertive}, new: &Abort {reason: Staleness, discord: Permissive, refresh: None}}),
+               xdb.assign(Uuid::nil(), &Abort {reason: Staleness, discord: Permissive, refresh: None}));
     assert_eq!(Some(&Commit {safepoint: 7, discord: Assertive}), xdb.get(&Uuid::nil()));
 }
 
@@ -83,6 +83,6 @@ fn xdb_assign_permissive_over_permissive_with_conflict() {
     assert_eq!(Ok(New(&Commit {safepoint: 7, discord: Permissive})),
                xdb.assign(Uuid::nil(), &Commit {safepoint: 7, discord: Permissive}));
     assert_eq!(Ok(Existing(&Commit {safepoint: 7, discord: Permissive})),
-               xdb.assign(Uuid::nil(), &Abort {reason: Staleness, discord: Permissive}));
+               xdb.assign(Uuid::nil(), &Abort {reason: Staleness, discord: Permissive, refresh: None}));
     assert_eq!(Some(&Commit {safepoint: 7, discord: Permissive}), xdb.get(&Uuid::nil()));
 }
\ No newline at end of file
@@ -9,7 +9,7 @@ fn init_log() {
     let _ = env_logger::builder().is_test(true).try_init();
 }
 
-pub fn dfs<S>(model: &Model<S>) {
+fn check<S>(model: &Model<S>) -> CheckResult {
     init_log();
     let (result, elapsed) = timed(|| {
         let config = checker::Config::default().with_sublevel(Sublevel::Fine);
@@ -30,6 +30,11 @@ pub fn dfs<S>(model: &Model<S>) {
         rate_s,
         stats
     );
+    result
+}
+
+pub fn dfs<S>(model: &Model<S>) {
+    let result = check(model);
     if let CheckResult::Fail(fail) = &result {
         log::error!("fail trace:\n{}", fail.trace.prettify(&model));
     } else if let CheckResult::Deadlock(deadlock) = &result {
@@ -38,6 +43,17 @@ pub fn dfs<S>(model: &Model<S>) {
     assert!(matches!(result, CheckResult::Pass(_)), "{:?}", result);
 }
 
+// checks a model that is expected to be breached, returning the error
+pub fn dfs_breach<S>(model: &Model<S>) -> String {
+    match check(model) {
+        CheckResult::Fail(fail) => {
+            log::debug!("fail trace:\n{}", fail.trace.prettify(model));
+            fail.error
+        }
+        result => panic!("expected a breach, got {:?}", result),
+    }
+}
+
 pub fn sim<S>(model: &Model<S>, max_schedules: usize) {
     init_log();
     let seed = seed();
@@ -4,7 +4,11 @@ mod utils;
 
 mod bank_model;
 mod blind_model;
+mod counter_model;
 mod marbles_model;
 mod monotonic_model;
+mod observers_model;
+mod partitioned_bank_model;
 mod roster_model;
+mod skew_model;
 mod swaps_model;
@@ -36,6 +36,8 @@ struct MarblesCfg<'a> {
     num_cohorts: usize,
     txns_per_cohort: usize,
     extents: &'a [usize],
+    keyed: bool,   // whether commits carry the safepoint of each change
+    precise: bool, // whether certifiers leave their assertive aborts unlearned
     name: &'a str,
 }
 
@@ -44,8 +46,18 @@ fn build_model(cfg: MarblesCfg) -> Model<SystemState> {
     let values = (0..cfg.num_values).map(|i| (i % 2) as i32).collect::<Vec<_>>();
     let num_cohorts = cfg.num_cohorts;
     let num_certifiers = cfg.extents.len();
-    let mut model = Model::new(move || SystemState::new(num_cohorts, &values, num_certifiers))
-        .with_name(cfg.name.into());
+    let (keyed, precise) = (cfg.keyed, cfg.precise);
+    let mut model = Model::new(move || {
+        let mut system = SystemState::new(num_cohorts, &values, num_certifiers);
+        if keyed {
+            system = system.with_key_safepoints(|item| format!("item-{}", item));
+        }
+        if precise {
+            system = system.with_precise_aborts();
+        }
+        system
+    })
+    .with_name(cfg.name.into());
 
     let txns_per_cohort = cfg.txns_per_cohort;
     for cohort_index in 0..cfg.num_cohorts {
@@ -84,6 +96,7 @@ fn build_model(cfg: MarblesCfg) -> Model<SystemState> {
                     writeset,
                     readvers,
                     snapshot,
+                    ..Default::default()
                 },
                 statemap,
             })));
@@ -114,6 +127,8 @@ fn dfs_marbles_1x1() {
         num_cohorts: 1,
         txns_per_cohort: 1,
         extents: &[1],
+        keyed: false,
+        precise: false,
         name: name_of(&dfs_marbles_1x1)
     }));
 }
@@ -125,6 +140,8 @@ fn dfs_marbles_1x2() {
         num_cohorts: 1,
         txns_per_cohort: 2,
         extents: &[2],
+        keyed: false,
+        precise: false,
         name: name_of(&dfs_marbles_1x2)
     }));
 }
@@ -137,6 +154,8 @@ fn dfs_marbles_2x1() {
         num_cohorts: 2,
         txns_per_cohort: 1,
         extents: &[2],
+        keyed: false,
+        precise: false,
         name: name_of(&dfs_marbles_2x1)
     }));
 }
@@ -149,6 +168,8 @@ fn dfs_marbles_2x2() {
         num_cohorts: 2,
         txns_per_cohort: 2,
         extents: &[4],
+        keyed: false,
+        precise: false,
         name: name_of(&dfs_marbles_2x2)
     }));
 }
@@ -160,6 +181,8 @@ fn sim_marbles_1x1() {
         num_cohorts: 1,
         txns_per_cohort: 1,
         extents: &[1],
+        keyed: false,
+        precise: false,
         name: name_of(&sim_marbles_1x1)
     }), 10);
 }
@@ -171,6 +194,8 @@ fn sim_marbles_2x1() {
         num_cohorts: 2,
         txns_per_cohort: 1,
         extents: &[2],
+        keyed: false,
+        precise: false,
         name: name_of(&sim_marbles_2x1)
     }), 20);
 }
@@ -182,6 +207,8 @@ fn sim_marbles_2x2() {
         num_cohorts: 2,
         txns_per_cohort: 2,
         extents: &[4],
+        keyed: false,
+        precise: false,
         name: name_of(&sim_marbles_2x2)
     }), 40);
 }
@@ -193,6 +220,8 @@ fn sim_marbles_3x1() {
         num_cohorts: 3,
         txns_per_cohort: 1,
         extents: &[3],
+        keyed: false,
+        precise: false,
         name: name_of(&sim_marbles_3x1)
     }), 40);
 }
@@ -204,6 +233,8 @@ fn sim_marbles_3x2() {
         num_cohorts: 3,
         txns_per_cohort: 2,
         extents: &[6],
+        keyed: false,
+        precise: false,
         name: name_of(&sim_marbles_3x2)
     }), 80);
 }
@@ -215,6 +246,8 @@ fn sim_marbles_4x1() {
         num_cohorts: 4,
         txns_per_cohort: 1,
         extents: &[4],
+        keyed: false,
+        precise: false,
         name: name_of(&sim_marbles_4x1)
     }), 80);
 }
@@ -226,6 +259,8 @@ fn sim_marbles_4x2_2x1() {
         num_cohorts: 4,
         txns_per_cohort: 2,
         extents: &[1, 1],
+        keyed: false,
+        precise: false,
         name: name_of(&sim_marbles_4x2_2x1),
     }), 160);
 }
@@ -237,6 +272,60 @@ fn sim_marbles_4x2_2x8() {
         num_cohorts: 4,
         txns_per_cohort: 2,
         extents: &[8, 8],
+        keyed: false,
+        precise: false,
         name: name_of(&sim_marbles_4x2_2x8),
     }), 160);
 }
+
+#[test]
+fn sim_marbles_3x2_keyed() {
+    sim(&build_model(MarblesCfg {
+        num_values: 2,
+        num_cohorts: 3,
+        txns_per_cohort: 2,
+        extents: &[6],
+        keyed: true,
+        precise: false,
+        name: name_of(&sim_marbles_3x2_keyed)
+    }), 80);
+}
+
+#[test]
+fn sim_marbles_4x2_2x8_keyed() {
+    sim(&build_model(MarblesCfg {
+        num_values: 2,
+        num_cohorts: 4,
+        txns_per_cohort: 2,
+        extents: &[8, 8],
+        keyed: true,
+        precise: false,
+        name: name_of(&sim_marbles_4x2_2x8_keyed)
+    }), 160);
+}
+
+#[test]
+fn sim_marbles_3x2_precise() {
+    sim(&build_model(MarblesCfg {
+        num_values: 2,
+        num_cohorts: 3,
+        txns_per_cohort: 2,
+        extents: &[6],
+        keyed: false,
+        precise: true,
+        name: name_of(&sim_marbles_3x2_precise)
+    }), 80);
+}
+
+#[test]
+fn sim_marbles_4x2_asymmetric_precise() {
+    sim(&build_model(MarblesCfg {
+        num_values: 2,
+        num_cohorts: 4,
+        txns_per_cohort: 2,
+        extents: &[1, 8],
+        keyed: false,
+        precise: true,
+        name: name_of(&sim_marbles_4x2_asymmetric_precise)
+    }), 160);
+}
@@ -93,6 +93,7 @@ fn build_model(cfg: MonotonicCfg) -> Model<SystemState> {
                     writeset,
                     readvers,
                     snapshot,
+                    ..Default::default()
                 },
                 statemap,
             })));
@@ -125,6 +126,7 @@ fn build_model(cfg: MonotonicCfg) -> Model<SystemState> {
                     writeset,
                     readvers,
                     snapshot,
+                    ..Default::default()
                 },
                 statemap,
             })));
@@ -0,0 +1,276 @@
+use std::rc::Rc;
+
+use stride::examiner::Record;
+use stride::havoc::model::{ActionResult, Context, Model, name_of, rand_element};
+use stride::havoc::model::ActionResult::{Blocked, Breached, Joined, Ran};
+use stride::havoc::model::Retention::{Strong, Weak};
+
+use crate::fixtures::schema::CandidateData;
+use crate::fixtures::schema::MessageKind::CandidateMessage;
+use crate::fixtures::xdb::Xdb;
+use crate::harness::{dfs, sim};
+use crate::utils::uuidify;
+
+use super::fixtures::*;
+
+// The bank model, with read-only observers that query the certifier on what their cohort's replica
+// holds, rather than publishing a candidate. Whenever the certifier admits an observation, some
+// prefix of the log must account for it: each account observed holds the last write of that
+// prefix, committed or not yet decided.
+#[derive(Debug)]
+struct ObservedState {
+    system: SystemState,
+    observations: Vec<usize>, // the queries made by each observer
+}
+
+impl CohortState for ObservedState {
+    fn cohorts(&mut self) -> &mut [Cohort] {
+        &mut self.system.cohorts
+    }
+}
+
+impl CertifierState for ObservedState {
+    fn certifiers(&mut self) -> &mut [Certifier] {
+        &mut self.system.certifiers
+    }
+}
+
+impl XdbState for ObservedState {
+    fn xdb(&mut self) -> &mut Xdb {
+        &mut self.system.xdb
+    }
+}
+
+fn asserter(values: &[i32], cohort_index: usize) -> impl Fn(&[Cohort]) -> AfterCheck {
+    let expected_sum = values.iter().sum::<i32>();
+    move |_| {
+        Box::new(move |after| {
+            let replica = &after[cohort_index].replica;
+            let computed_sum = replica.items.iter().map(|&(item_val, _)| item_val).sum::<i32>();
+            if expected_sum != computed_sum {
+                Some(format!("expected: {}, computed: {} for {:?}", expected_sum, computed_sum, replica))
+            } else {
+                None
+            }
+        })
+    }
+}
+
+// the versions of the prefixes of the log that account for the observed item versions
+fn accounting_prefixes(system: &SystemState, observed: &[(i32, u64)]) -> std::ops::Range<u64> {
+    let candidates = system.certifiers[0].stream.find(|msg| msg.as_candidate().is_some());
+    let mut prefixes = 0..u64::MAX;
+    for (item, &(_, item_ver)) in observed.iter().enumerate() {
+        prefixes.start = std::cmp::max(prefixes.start, item_ver);
+        let overwrite = candidates.iter().find(|(offset, msg)| {
+            let candidate = msg.as_candidate().unwrap();
+            let aborted = matches!(system.xdb.get(&candidate.rec.xid), Some(outcome) if outcome.is_abort());
+            *offset as u64 > item_ver
+                && !aborted
+                && candidate.statemap.changes.iter().any(|&(change_item, _)| change_item == item)
+        });
+        if let Some((offset, _)) = overwrite {
+            prefixes.end = std::cmp::min(prefixes.end, *offset as u64);
+        }
+    }
+    prefixes
+}
+
+fn observer_action(
+    observer_index: usize,
+    cohort_index: usize,
+    observations_per_observer: usize,
+) -> impl Fn(&mut ObservedState, &mut dyn Context) -> ActionResult {
+    move |s, _| {
+        let observation = s.observations[observer_index];
+        let replica = &s.system.cohorts[cohort_index].replica;
+        let observed = replica.items.clone();
+        let cpt_readvers = observed.iter().map(|&(_, item_ver)| item_ver).collect();
+        let (readvers, snapshot) = Record::compress(cpt_readvers, replica.ver);
+        let rec = Record {
+            xid: uuidify(observer_index, observation),
+            readset: (0..observed.len()).map(|i| format!("item-{}", i)).collect(),
+            writeset: vec![],
+            readvers,
+            snapshot,
+            ..Default::default()
+        };
+
+        let certifier = &mut s.system.certifiers[0];
+        let interned = certifier.interner.intern_record(&rec);
+        let outcome = certifier.examiner.query(&interned);
+        certifier.interner.release_all(&interned.readset);
+        log::trace!("Queried {:?} with {:?}", observed, outcome);
+        if outcome.is_commit() && accounting_prefixes(&s.system, &observed).is_empty() {
+            return Breached(format!("observed a non-serializable snapshot {:?} as {:?}", observed, outcome));
+        }
+
+        s.observations[observer_index] += 1;
+        if observation + 1 == observations_per_observer {
+            Joined
+        } else {
+            Ran
+        }
+    }
+}
+
+struct ObserversCfg<'a> {
+    values: &'a [i32],
+    num_cohorts: usize,
+    txns_per_cohort: usize,
+    num_observers: usize,
+    observations_per_observer: usize,
+    extent: usize,
+    name: &'a str,
+}
+
+fn build_model(cfg: ObserversCfg) -> Model<ObservedState> {
+    let (num_cohorts, num_observers) = (cfg.num_cohorts, cfg.num_observers);
+    let values = cfg.values;
+    let mut model = Model::new(move || ObservedState {
+        system: SystemState::new(num_cohorts, values, 1),
+        observations: vec![0; num_observers],
+    })
+    .with_name(cfg.name.into());
+
+    for cohort_index in 0..num_cohorts {
+        let itemset = (0..values.len()).map(|i| format!("item-{}", i)).collect::<Vec<_>>();
+        let txns_per_cohort = cfg.txns_per_cohort;
+        model.add_action(format!("initiator-{}", cohort_index), Weak, move |s, c| {
+            let run = s.system.cohort_txns(cohort_index);
+            let cohort = &s.system.cohorts[cohort_index];
+            let accounts = cohort.replica.items.iter().copied().enumerate().collect::<Vec<_>>();
+
+            // transfer at least half of a funded account's value to another account
+            let from_accounts = accounts.iter().filter(|&&(_, (item_val, _))| item_val > 0).collect::<Vec<_>>();
+            if from_accounts.is_empty() {
+                return Blocked;
+            }
+            let &&(from, (from_val, from_ver)) = rand_element(c, &from_accounts);
+            let to_accounts = accounts.iter().filter(|&&(item, _)| item != from).collect::<Vec<_>>();
+            let &&(to, (to_val, to_ver)) = rand_element(c, &to_accounts);
+            let xfer_amount = (from_val + 1) / 2;
+
+            let readset = vec![itemset[from].clone(), itemset[to].clone()];
+            let (readvers, snapshot) = Record::compress(vec![from_ver, to_ver], cohort.replica.ver);
+            let changes = &[(from, from_val - xfer_amount), (to, to_val + xfer_amount)];
+            cohort.stream.produce(Rc::new(CandidateMessage(CandidateData {
+                rec: Record {
+                    xid: uuidify(cohort_index, run),
+                    readset: readset.clone(),
+                    writeset: readset,
+                    readvers,
+                    snapshot,
+                    ..Default::default()
+                },
+                statemap: Statemap::map(changes, Op::Set),
+            })));
+            if run + 1 == txns_per_cohort {
+                Joined
+            } else {
+                Ran
+            }
+        });
+        model.add_action(
+            format!("updater-{}", cohort_index),
+            Weak,
+            updater_action(cohort_index, asserter(values, cohort_index)),
+        );
+        model.add_action(
+            format!("replicator-{}", cohort_index),
+            Weak,
+            replicator_action(cohort_index, asserter(values, cohort_index)),
+        );
+    }
+    for observer_index in 0..num_observers {
+        model.add_action(
+            format!("observer-{}", observer_index),
+            Weak,
+            observer_action(observer_index, observer_index % num_cohorts, cfg.observations_per_observer),
+        );
+    }
+    model.add_action("certifier-0".into(), Weak, certifier_action(0, cfg.extent));
+    model.add_action(
+        "supervisor".into(),
+        Strong,
+        supervisor_action(cfg.num_cohorts * cfg.txns_per_cohort),
+    );
+    model
+}
+
+#[test]
+fn dfs_observers_2x1x1_1x1() {
+    dfs(&build_model(ObserversCfg {
+        values: &[101, 103],
+        num_cohorts: 1,
+        txns_per_cohort: 1,
+        num_observers: 1,
+        observations_per_observer: 1,
+        extent: 1,
+        name: name_of(&dfs_observers_2x1x1_1x1),
+    }));
+}
+
+#[test]
+fn sim_observers_3x2x2_2x4() {
+    sim(
+        &build_model(ObserversCfg {
+            values: &[101, 103, 105],
+            num_cohorts: 2,
+            txns_per_cohort: 2,
+            num_observers: 2,
+            observations_per_observer: 4,
+            extent: 4,
+            name: name_of(&sim_observers_3x2x2_2x4),
+        }),
+        80,
+    );
+}
+
+#[test]
+fn sim_observers_4x3x2_3x4() {
+    sim(
+        &build_model(ObserversCfg {
+            values: &[101, 103, 105, 107],
+            num_cohorts: 3,
+            txns_per_cohort: 2,
+            num_observers: 3,
+            observations_per_observer: 4,
+            extent: 6,
+            name: name_of(&sim_observers_4x3x2_3x4),
+        }),
+        80,
+    );
+}
+
+#[test]
+fn sim_observers_6x4x3_4x8() {
+    sim(
+        &build_model(ObserversCfg {
+            values: &[101, 103, 105, 107, 109, 111],
+            num_cohorts: 4,
+            txns_per_cohort: 3,
+            num_observers: 4,
+            observations_per_observer: 8,
+            extent: 12,
+            name: name_of(&sim_observers_6x4x3_4x8),
+        }),
+        200,
+    );
+}
+
+#[test]
+fn sim_observers_6x4x2_2x6_small_extent() {
+    sim(
+        &build_model(ObserversCfg {
+            values: &[101, 103, 105, 107, 109, 111],
+            num_cohorts: 4,
+            txns_per_cohort: 2,
+            num_observers: 2,
+            observations_per_observer: 6,
+            extent: 2,
+            name: name_of(&sim_observers_6x4x2_2x6_small_extent),
+        }),
+        160,
+    );
+}
@@ -0,0 +1,276 @@
+use std::rc::Rc;
+
+use stride::examiner::Record;
+use stride::havoc::model::{ActionResult, Context, Model, name_of, rand_element};
+use stride::havoc::model::ActionResult::{Blocked, Breached, Joined, Ran};
+use stride::havoc::model::Retention::{Strong, Weak};
+
+use crate::fixtures::schema::CandidateData;
+use crate::fixtures::schema::MessageKind::CandidateMessage;
+use crate::harness::{dfs, sim};
+use crate::utils::uuidify;
+
+use super::fixtures::*;
+
+// The bank model, with the accounts split across partitions, so that a transfer between accounts
+// of different partitions commits in both or neither. Each replica holds the accounts of its own
+// partition, none of which may go negative; once every decision is installed, the accounts of a
+// cohort's replicas must hold the sum they started with.
+fn asserter(
+    num_partitions: usize,
+    replica_index: usize,
+) -> impl Fn(&[Cohort]) -> AfterCheck {
+    move |_| {
+        Box::new(move |after| {
+            let replica = &after[replica_index].replica;
+            let partition_index = replica_index % num_partitions;
+            let owned = replica.items.iter().skip(partition_index).step_by(num_partitions);
+            if owned.clone().any(|&(item_val, _)| item_val < 0) {
+                return Some(format!("account negative: {:?}", replica));
+            }
+            None
+        })
+    }
+}
+
+fn supervisor(values: &[i32], expected_txns: usize) -> impl Fn(&mut PartitionedState, &mut dyn Context) -> ActionResult {
+    let expected_sum = values.iter().sum::<i32>();
+    move |s, _| {
+        let num_partitions = s.num_partitions();
+        let cohorts = &s.cohorts;
+        if cohorts.iter().any(|cohort| cohort.stream.offset() != expected_txns * 2 + 1) {
+            return Blocked;
+        }
+        for replicas in cohorts.chunks(num_partitions) {
+            let computed_sum = replicas
+                .iter()
+                .enumerate()
+                .flat_map(|(partition_index, cohort)| {
+                    cohort.replica.items.iter().skip(partition_index).step_by(num_partitions)
+                })
+                .map(|&(item_val, _)| item_val)
+                .sum::<i32>();
+            if expected_sum != computed_sum {
+                return Breached(format!(
+                    "expected: {}, computed: {} for {:?}",
+                    expected_sum, computed_sum, replicas
+                ));
+            }
+        }
+        Joined
+    }
+}
+
+struct PartitionedBankCfg<'a> {
+    values: &'a [i32],
+    num_partitions: usize,
+    num_cohorts: usize,
+    txns_per_cohort: usize,
+    extents: &'a [usize],
+    transfers: Option<&'a [(usize, usize)]>, // the accounts of each cohort's transfers, if not random
+    name: &'a str,
+}
+
+fn build_model(cfg: PartitionedBankCfg) -> Model<PartitionedState> {
+    let (num_partitions, num_cohorts) = (cfg.num_partitions, cfg.num_cohorts);
+    let num_certifiers = cfg.extents.len();
+    let values = cfg.values;
+    let mut model = Model::new(move || PartitionedState::new(num_partitions, num_cohorts, values, num_certifiers))
+        .with_name(cfg.name.into());
+
+    for cohort_index in 0..num_cohorts {
+        let itemset = (0..values.len()).map(|i| format!("item-{}", i)).collect::<Vec<_>>();
+        let txns_per_cohort = cfg.txns_per_cohort;
+        let transfers = cfg.transfers.map(<[_]>::to_vec);
+        model.add_action(format!("initiator-{}", cohort_index), Weak, move |s, c| {
+            let run = s.cohort_txns(cohort_index);
+            let replicas = &s.cohorts[cohort_index * num_partitions..(cohort_index + 1) * num_partitions];
+            // each account as seen by the replica of its partition
+            let accounts = (0..itemset.len())
+                .map(|item| replicas[item % num_partitions].replica.items[item])
+                .collect::<Vec<_>>();
+
+            // pick a 'from' account with sufficient funds, and a 'to' account other than it
+            let from_accounts = (0..accounts.len()).filter(|&item| accounts[item].0 > 0).collect::<Vec<_>>();
+            if from_accounts.is_empty() {
+                return Blocked;
+            }
+            let (from, to) = match &transfers {
+                Some(transfers) => transfers[run],
+                None => {
+                    let from = *rand_element(c, &from_accounts);
+                    let to_accounts = (0..accounts.len()).filter(|&item| item != from).collect::<Vec<_>>();
+                    (from, *rand_element(c, &to_accounts))
+                }
+            };
+
+            // transfer at least half of the value in the 'from' account
+            let (from_val, to_val) = (accounts[from].0, accounts[to].0);
+            let xfer_amount = (from_val + 1) / 2;
+            let changes = [(from, from_val - xfer_amount), (to, to_val + xfer_amount)];
+
+            // slice the transfer by partition, each slice read from the replica of its partition
+            for (partition_index, cohort) in replicas.iter().enumerate() {
+                let items = [from, to]
+                    .iter()
+                    .copied()
+                    .filter(|item| item % num_partitions == partition_index)
+                    .collect::<Vec<_>>();
+                let readset = items.iter().map(|&item| itemset[item].clone()).collect::<Vec<_>>();
+                let cpt_readvers = items.iter().map(|&item| accounts[item].1).collect();
+                let (readvers, snapshot) = Record::compress(cpt_readvers, cohort.replica.ver);
+                let changes = changes
+                    .iter()
+                    .copied()
+                    .filter(|(item, _)| item % num_partitions == partition_index)
+                    .collect::<Vec<_>>();
+                cohort.stream.produce(Rc::new(CandidateMessage(CandidateData {
+                    rec: Record {
+                        xid: uuidify(cohort_index, run),
+                        readset: readset.clone(),
+                        writeset: readset,
+                        readvers,
+                        snapshot,
+                        ..Default::default()
+                    },
+                    statemap: Statemap::map(&changes, Op::Set),
+                })));
+            }
+            if run + 1 == txns_per_cohort {
+                Joined
+            } else {
+                Ran
+            }
+        });
+        for partition_index in 0..num_partitions {
+            let replica_index = cohort_index * num_partitions + partition_index;
+            model.add_action(
+                format!("replicator-{}-{}", cohort_index, partition_index),
+                Weak,
+                replicator_action(replica_index, asserter(num_partitions, replica_index)),
+            );
+        }
+    }
+    for partition_index in 0..num_partitions {
+        for (certifier_index, &extent) in cfg.extents.iter().enumerate() {
+            model.add_action(
+                format!("certifier-{}-{}", partition_index, certifier_index),
+                Weak,
+                voter_action(partition_index, certifier_index, extent),
+            );
+        }
+    }
+    model.add_action("combiner".into(), Weak, combiner_action());
+    model.add_action(
+        "supervisor".into(),
+        Strong,
+        supervisor(values, cfg.num_cohorts * cfg.txns_per_cohort),
+    );
+    model
+}
+
+#[test]
+fn dfs_partitioned_bank_2x1x1() {
+    dfs(&build_model(PartitionedBankCfg {
+        values: &[101, 103],
+        num_partitions: 2,
+        num_cohorts: 1,
+        txns_per_cohort: 1,
+        extents: &[1],
+        transfers: None,
+        name: name_of(&dfs_partitioned_bank_2x1x1),
+    }));
+}
+
+// a transfer read from a stale replica is voted down by the partition of the account overwritten,
+// but not by the partition that holds only its other account, so it aborts globally
+#[test]
+fn dfs_partitioned_bank_3x1x2() {
+    dfs(&build_model(PartitionedBankCfg {
+        values: &[101, 103, 105],
+        num_partitions: 2,
+        num_cohorts: 1,
+        txns_per_cohort: 2,
+        extents: &[2],
+        transfers: Some(&[(0, 2), (0, 1)]),
+        name: name_of(&dfs_partitioned_bank_3x1x2),
+    }));
+}
+
+#[test]
+#[ignore]
+fn dfs_partitioned_bank_2x2x1() {
+    dfs(&build_model(PartitionedBankCfg {
+        values: &[101, 103],
+        num_partitions: 2,
+        num_cohorts: 2,
+        txns_per_cohort: 1,
+        extents: &[2],
+        transfers: None,
+        name: name_of(&dfs_partitioned_bank_2x2x1),
+    }));
+}
+
+#[test]
+fn sim_partitioned_bank_2x2x2() {
+    sim(
+        &build_model(PartitionedBankCfg {
+            values: &[101, 103],
+            num_partitions: 2,
+            num_cohorts: 2,
+            txns_per_cohort: 2,
+            extents: &[4],
+            transfers: None,
+            name: name_of(&sim_partitioned_bank_2x2x2),
+        }),
+        40,
+    );
+}
+
+#[test]
+fn sim_partitioned_bank_4x3x2() {
+    sim(
+        &build_model(PartitionedBankCfg {
+            values: &[101, 103, 105, 107],
+            num_partitions: 2,
+            num_cohorts: 3,
+            txns_per_cohort: 2,
+            extents: &[6],
+            transfers: None,
+            name: name_of(&sim_partitioned_bank_4x3x2),
+        }),
+        80,
+    );
+}
+
+#[test]
+fn sim_partitioned_bank_4x3x2_2x6() {
+    sim(
+        &build_model(PartitionedBankCfg {
+            values: &[101, 103, 105, 107],
+            num_partitions: 2,
+            num_cohorts: 3,
+            txns_per_cohort: 2,
+            extents: &[6, 6],
+            transfers: None,
+            name: name_of(&sim_partitioned_bank_4x3x2_2x6),
+        }),
+        80,
+    );
+}
+
+#[test]
+fn sim_partitioned_bank_3x4x2_small_extent() {
+    sim(
+        &build_model(PartitionedBankCfg {
+            values: &[101, 103, 105],
+            num_partitions: 3,
+            num_cohorts: 4,
+            txns_per_cohort: 2,
+            extents: &[2],
+            transfers: None,
+            name: name_of(&sim_partitioned_bank_3x4x2_small_extent),
+        }),
+        160,
+    );
+}
@@ -31,6 +31,8 @@ struct RosterCfg<'a> {
     num_cohorts: usize,
     txns_per_cohort: usize,
     extents: &'a [usize],
+    keyed: bool,   // whether commits carry the safepoint of each change
+    precise: bool, // whether certifiers leave their assertive aborts unlearned
     name: &'a str
 }
 
@@ -38,8 +40,18 @@ fn build_model(cfg: RosterCfg) -> Model<SystemState> {
     let num_cohorts = cfg.num_cohorts;
     let num_certifiers = cfg.extents.len();
     let values = cfg.values;
-    let mut model = Model::new(move || SystemState::new(num_cohorts, values, num_certifiers))
-        .with_name(cfg.name.into());
+    let (keyed, precise) = (cfg.keyed, cfg.precise);
+    let mut model = Model::new(move || {
+        let mut system = SystemState::new(num_cohorts, values, num_certifiers);
+        if keyed {
+            system = system.with_key_safepoints(|item| format!("item-{}", item));
+        }
+        if precise {
+            system = system.with_precise_aborts();
+        }
+        system
+    })
+    .with_name(cfg.name.into());
     let expected_txns = cfg.num_cohorts * cfg.txns_per_cohort;
     let num_values = values.len();
 
@@ -85,6 +97,7 @@ fn build_model(cfg: RosterCfg) -> Model<SystemState> {
                     writeset,
                     readvers,
                     snapshot,
+                    ..Default::default()
                 },
                 statemap,
             })));
@@ -124,6 +137,7 @@ fn build_model(cfg: RosterCfg) -> Model<SystemState> {
                     writeset,
                     readvers,
                     snapshot,
+                    ..Default::default()
                 },
                 statemap,
             })));
@@ -154,6 +168,8 @@ fn dfs_roster_1x1() {
         num_cohorts: 1,
         txns_per_cohort: 1,
         extents: &[1],
+        keyed: false,
+        precise: false,
         name: name_of(&dfs_roster_1x1)
     }));
 }
@@ -165,6 +181,8 @@ fn dfs_roster_1x2() {
         num_cohorts: 1,
         txns_per_cohort: 2,
         extents: &[2],
+        keyed: false,
+        precise: false,
         name: name_of(&dfs_roster_1x2)
     }));
 }
@@ -177,6 +195,8 @@ fn dfs_roster_2x1() {
         num_cohorts: 2,
         txns_per_cohort: 1,
         extents: &[2],
+        keyed: false,
+        precise: false,
         name: name_of(&dfs_roster_2x1)
     }));
 }
@@ -189,6 +209,8 @@ fn dfs_roster_2x2() {
         num_cohorts: 2,
         txns_per_cohort: 2,
         extents: &[4],
+        keyed: false,
+        precise: false,
         name: name_of(&dfs_roster_2x2)
     }))
 }
@@ -200,6 +222,8 @@ fn sim_roster_1x1() {
         num_cohorts: 1,
         txns_per_cohort: 1,
         extents: &[1],
+        keyed: false,
+        precise: false,
         name: name_of(&sim_roster_1x1)
     }), 10);
 }
@@ -211,6 +235,8 @@ fn sim_roster_2x1() {
         num_cohorts: 2,
         txns_per_cohort: 1,
         extents: &[2],
+        keyed: false,
+        precise: false,
         name: name_of(&sim_roster_2x1)
     }), 20);
 }
@@ -222,6 +248,8 @@ fn sim_roster_2x2() {
         num_cohorts: 2,
         txns_per_cohort: 2,
         extents: &[4],
+        keyed: false,
+        precise: false,
         name: name_of(&sim_roster_2x2)
     }), 40);
 }
@@ -233,6 +261,8 @@ fn sim_roster_3x1() {
         num_cohorts: 3,
         txns_per_cohort: 1,
         extents: &[3],
+        keyed: false,
+        precise: false,
         name: name_of(&sim_roster_3x1)
     }), 40);
 }
@@ -244,6 +274,8 @@ fn sim_roster_3x2() {
         num_cohorts: 3,
         txns_per_cohort: 2,
         extents: &[6],
+        keyed: false,
+        precise: false,
         name: name_of(&sim_roster_3x2)
     }), 80);
 }
@@ -255,6 +287,8 @@ fn sim_roster_4x1() {
         num_cohorts: 4,
         txns_per_cohort: 1,
         extents: &[4],
+        keyed: false,
+        precise: false,
         name: name_of(&sim_roster_4x1)
     }), 80);
 }
@@ -266,6 +300,8 @@ fn sim_roster_4x2_2x1() {
         num_cohorts: 4,
         txns_per_cohort: 1,
         extents: &[1, 1],
+        keyed: false,
+        precise: false,
         name: name_of(&sim_roster_4x2_2x1)
     }), 160);
 }
@@ -277,6 +313,60 @@ fn sim_roster_4x2_2x8() {
         num_cohorts: 3,
         txns_per_cohort: 2,
         extents: &[8, 8],
+        keyed: false,
+        precise: false,
         name: name_of(&sim_roster_4x2_2x8)
     }), 160);
 }
+
+#[test]
+fn sim_roster_3x2_keyed() {
+    sim(&build_model(RosterCfg {
+        values: &[0, 1, 0],
+        num_cohorts: 3,
+        txns_per_cohort: 2,
+        extents: &[6],
+        keyed: true,
+        precise: false,
+        name: name_of(&sim_roster_3x2_keyed)
+    }), 80);
+}
+
+#[test]
+fn sim_roster_4x2_2x8_keyed() {
+    sim(&build_model(RosterCfg {
+        values: &[0, 1, 0, 1],
+        num_cohorts: 3,
+        txns_per_cohort: 2,
+        extents: &[8, 8],
+        keyed: true,
+        precise: false,
+        name: name_of(&sim_roster_4x2_2x8_keyed)
+    }), 160);
+}
+
+#[test]
+fn sim_roster_3x2_precise() {
+    sim(&build_model(RosterCfg {
+        values: &[0, 1, 0],
+        num_cohorts: 3,
+        txns_per_cohort: 2,
+        extents: &[6],
+        keyed: false,
+        precise: true,
+        name: name_of(&sim_roster_3x2_precise)
+    }), 80);
+}
+
+#[test]
+fn sim_roster_4x2_asymmetric_precise() {
+    sim(&build_model(RosterCfg {
+        values: &[0, 1, 0, 1],
+        num_cohorts: 3,
+        txns_per_cohort: 2,
+        extents: &[1, 8],
+        keyed: false,
+        precise: true,
+        name: name_of(&sim_roster_4x2_asymmetric_precise)
+    }), 160);
+}
@@ -0,0 +1,139 @@
+use std::rc::Rc;
+
+use stride::examiner::Isolation;
+use stride::examiner::Isolation::{Serializable, Snapshot};
+use stride::examiner::Record;
+use stride::havoc::model::{Model, name_of};
+use stride::havoc::model::ActionResult::{Joined, Ran};
+use stride::havoc::model::Retention::{Strong, Weak};
+
+use crate::fixtures::schema::CandidateData;
+use crate::fixtures::schema::MessageKind::CandidateMessage;
+use crate::harness::{dfs, dfs_breach, sim};
+use crate::utils::uuidify;
+
+use super::fixtures::*;
+
+// Two linked accounts, each owned by a cohort. A cohort may withdraw from its own account so long
+// as the combined balance covers the withdrawal. Each withdrawal reads both accounts but writes
+// only one, so concurrent withdrawals are a write skew: admitted under snapshot isolation, which
+// leaves the accounts overdrawn, and rejected under serializability.
+const BALANCES: &[i32] = &[60, 60];
+const WITHDRAWAL: i32 = 100;
+
+fn asserter() -> impl Fn(&[Cohort]) -> AfterCheck {
+    move |_| {
+        Box::new(move |after| {
+            for cohort in after {
+                let combined = cohort.replica.items.iter().map(|&(item_val, _)| item_val).sum::<i32>();
+                if combined < 0 {
+                    return Some(format!("overdrawn by {} for {:?}", -combined, cohort.replica));
+                }
+            }
+            None
+        })
+    }
+}
+
+struct SkewCfg<'a> {
+    isolation: Isolation,
+    txns_per_cohort: usize,
+    extents: &'a [usize],
+    name: &'a str,
+}
+
+fn build_model(cfg: SkewCfg) -> Model<SystemState> {
+    let num_cohorts = BALANCES.len();
+    let num_certifiers = cfg.extents.len();
+    let isolation = cfg.isolation;
+    let mut model = Model::new(move || {
+        SystemState::new(num_cohorts, BALANCES, num_certifiers).with_isolation(isolation)
+    })
+    .with_name(cfg.name.into());
+
+    let txns_per_cohort = cfg.txns_per_cohort;
+    for cohort_index in 0..num_cohorts {
+        let itemset = (0..num_cohorts).map(|i| format!("item-{}", i)).collect::<Vec<_>>();
+        model.add_action(format!("initiator-{}", cohort_index), Weak, move |s, _| {
+            let run = s.cohort_txns(cohort_index);
+            let cohort = &mut s.cohorts[cohort_index];
+            let combined = cohort.replica.items.iter().map(|&(item_val, _)| item_val).sum::<i32>();
+            let cpt_readvers = cohort.replica.items.iter().map(|&(_, item_ver)| item_ver).collect();
+            let cpt_snapshot = cohort.replica.ver;
+            let (writeset, changes) = if combined >= WITHDRAWAL {
+                (vec![itemset[cohort_index].clone()], vec![(cohort_index, Op::Add(-WITHDRAWAL))])
+            } else {
+                (vec![], vec![])
+            };
+            let (readvers, snapshot) = Record::compress(cpt_readvers, cpt_snapshot);
+            cohort.stream.produce(Rc::new(CandidateMessage(CandidateData {
+                rec: Record {
+                    xid: uuidify(cohort_index, run),
+                    readset: itemset.clone(),
+                    writeset,
+                    readvers,
+                    snapshot,
+                    ..Default::default()
+                },
+                statemap: Statemap::new(changes),
+            })));
+            if run + 1 == txns_per_cohort {
+                Joined
+            } else {
+                Ran
+            }
+        });
+        model.add_action(format!("updater-{}", cohort_index), Weak, updater_action(cohort_index, asserter()));
+        model.add_action(format!("replicator-{}", cohort_index), Weak, replicator_action(cohort_index, asserter()));
+    }
+    for (certifier_index, &extent) in cfg.extents.iter().enumerate() {
+        model.add_action(
+            format!("certifier-{}", certifier_index),
+            Weak,
+            certifier_action(certifier_index, extent),
+        );
+    }
+    model.add_action("supervisor".into(), Strong, supervisor_action(num_cohorts * cfg.txns_per_cohort));
+    model
+}
+
+#[test]
+fn dfs_skew_serializable_2x1() {
+    dfs(&build_model(SkewCfg {
+        isolation: Serializable,
+        txns_per_cohort: 1,
+        extents: &[2],
+        name: name_of(&dfs_skew_serializable_2x1)
+    }));
+}
+
+#[test]
+fn dfs_skew_snapshot_2x1() {
+    let error = dfs_breach(&build_model(SkewCfg {
+        isolation: Snapshot,
+        txns_per_cohort: 1,
+        extents: &[2],
+        name: name_of(&dfs_skew_snapshot_2x1)
+    }));
+    assert!(error.starts_with("overdrawn by 80"), "{}", error);
+}
+
+#[test]
+fn sim_skew_serializable_2x2() {
+    sim(&build_model(SkewCfg {
+        isolation: Serializable,
+        txns_per_cohort: 2,
+        extents: &[4],
+        name: name_of(&sim_skew_serializable_2x2)
+    }), 40);
+}
+
+#[test]
+fn sim_skew_serializable_2x2_2x2() {
+    sim(&build_model(SkewCfg {
+        isolation: Serializable,
+        txns_per_cohort: 2,
+        extents: &[2, 2],
+        name: name_of(&sim_skew_serializable_2x2_2x2)
+    }), 80);
+}
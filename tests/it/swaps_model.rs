@@ -2,7 +2,7 @@ use std::rc::Rc;
 
 use stride::examiner::Record;
 use stride::havoc::model::{Model, name_of};
-use stride::havoc::model::ActionResult::{Joined, Ran};
+use stride::havoc::model::ActionResult::{Blocked, Joined, Ran};
 use stride::havoc::model::Retention::{Strong, Weak};
 
 use crate::fixtures::schema::CandidateData;
@@ -42,6 +42,8 @@ struct SwapsCfg<'a> {
     combos: &'a [(usize, usize)],
     txns_per_cohort: usize,
     extents: &'a [usize],
+    retrying: bool, // whether cohorts hold off retrying until caught up with the refresh hint
+    precise: bool,  // whether certifiers leave their assertive aborts unlearned
     name: &'a str,
 }
 
@@ -49,13 +51,26 @@ fn build_model(cfg: SwapsCfg) -> Model<SystemState> {
     let num_cohorts = cfg.combos.len();
     let num_certifiers = cfg.extents.len();
     let values = cfg.values;
-    let mut model = Model::new(move || SystemState::new(num_cohorts, values, num_certifiers))
-        .with_name(cfg.name.into());
+    let (retrying, precise) = (cfg.retrying, cfg.precise);
+    let mut model = Model::new(move || {
+        let mut system = SystemState::new(num_cohorts, values, num_certifiers);
+        if retrying {
+            system = system.with_refresh_hints();
+        }
+        if precise {
+            system = system.with_precise_aborts();
+        }
+        system
+    })
+    .with_name(cfg.name.into());
 
     let txns_per_cohort = cfg.txns_per_cohort;
     for (cohort_index, &(p, q)) in cfg.combos.iter().enumerate() {
         let itemset = [format!("item-{}", p), format!("item-{}", q)];
         model.add_action(format!("initiator-{}", cohort_index), Weak, move |s, _| {
+            if retrying && s.awaiting_retry(cohort_index) {
+                return Blocked;
+            }
             let run = s.cohort_txns(cohort_index);
             let cohort = &mut s.cohorts[cohort_index];
             let ((old_p_val, old_p_ver), (old_q_val, old_q_ver)) =
@@ -73,6 +88,7 @@ fn build_model(cfg: SwapsCfg) -> Model<SystemState> {
                         writeset: itemset.to_vec(),
                         readvers,
                         snapshot,
+                        ..Default::default()
                     },
                     statemap,
                 })));
@@ -115,6 +131,8 @@ fn dfs_swaps_1x1() {
         combos: &[(0, 1)],
         txns_per_cohort: 1,
         extents: &[1],
+        retrying: false,
+        precise: false,
         name: name_of(&dfs_swaps_1x1),
     }));
 }
@@ -126,6 +144,8 @@ fn dfs_swaps_1x2() {
         combos: &[(0, 1)],
         txns_per_cohort: 2,
         extents: &[2],
+        retrying: false,
+        precise: false,
         name: name_of(&dfs_swaps_1x2),
     }));
 }
@@ -138,6 +158,8 @@ fn dfs_swaps_2x1() {
         combos: &[(0, 1), (1, 2)],
         txns_per_cohort: 1,
         extents: &[2],
+        retrying: false,
+        precise: false,
         name: name_of(&dfs_swaps_2x1),
     }));
 }
@@ -150,6 +172,8 @@ fn dfs_swaps_2x2() {
         combos: &[(0, 1), (1, 2)],
         txns_per_cohort: 2,
         extents: &[4],
+        retrying: false,
+        precise: false,
         name: name_of(&dfs_swaps_2x2),
     }));
 }
@@ -162,6 +186,8 @@ fn dfs_swaps_3x1() {
         combos: &[(0, 1), (1, 2), (0, 2)],
         txns_per_cohort: 1,
         extents: &[3],
+        retrying: false,
+        precise: false,
         name: name_of(&dfs_swaps_3x1),
     }));
 }
@@ -174,6 +200,8 @@ fn sim_swaps_1x1() {
             combos: &[(0, 1)],
             txns_per_cohort: 1,
             extents: &[1],
+            retrying: false,
+            precise: false,
             name: name_of(&sim_swaps_1x1),
         }),
         10,
@@ -188,6 +216,8 @@ fn sim_swaps_2x1() {
             combos: &[(0, 1), (1, 2)],
             txns_per_cohort: 1,
             extents: &[2],
+            retrying: false,
+            precise: false,
             name: name_of(&sim_swaps_2x1),
         }),
         20,
@@ -202,6 +232,8 @@ fn sim_swaps_2x2() {
             combos: &[(0, 1), (1, 2)],
             txns_per_cohort: 2,
             extents: &[4],
+            retrying: false,
+            precise: false,
             name: name_of(&sim_swaps_2x2),
         }),
         40,
@@ -216,6 +248,8 @@ fn sim_swaps_3x1() {
             combos: &[(0, 1), (1, 2), (0, 2)],
             txns_per_cohort: 1,
             extents: &[3],
+            retrying: false,
+            precise: false,
             name: name_of(&sim_swaps_3x1),
         }),
         40,
@@ -230,6 +264,8 @@ fn sim_swaps_3x2() {
             combos: &[(0, 1), (1, 2), (0, 2)],
             txns_per_cohort: 2,
             extents: &[6],
+            retrying: false,
+            precise: false,
             name: name_of(&sim_swaps_3x2),
         }),
         80,
@@ -244,6 +280,8 @@ fn sim_swaps_4x1() {
             combos: &[(0, 1), (1, 2), (2, 3)],
             txns_per_cohort: 1,
             extents: &[4],
+            retrying: false,
+            precise: false,
             name: name_of(&sim_swaps_4x1),
         }),
         80,
@@ -258,6 +296,8 @@ fn sim_swaps_4x2_2x1() {
             combos: &[(0, 1), (1, 2), (2, 3)],
             txns_per_cohort: 2,
             extents: &[1, 1],
+            retrying: false,
+            precise: false,
             name: name_of(&sim_swaps_4x2_2x1),
         }),
         160,
@@ -272,6 +312,8 @@ fn sim_swaps_4x2_asymmetric() {
             combos: &[(0, 1), (1, 2), (2, 3)],
             txns_per_cohort: 2,
             extents: &[1, 2],
+            retrying: false,
+            precise: false,
             name: name_of(&sim_swaps_4x2_asymmetric),
         }),
         160,
@@ -286,8 +328,87 @@ fn sim_swaps_4x2_2x8() {
             combos: &[(0, 1), (1, 2), (2, 3)],
             txns_per_cohort: 2,
             extents: &[8, 8],
+            retrying: false,
+            precise: false,
             name: name_of(&sim_swaps_4x2_2x8),
         }),
         160,
     );
 }
+
+#[test]
+fn dfs_swaps_1x2_retrying() {
+    dfs(&build_model(SwapsCfg {
+        values: &[101, 103],
+        combos: &[(0, 1)],
+        txns_per_cohort: 2,
+        extents: &[2],
+        retrying: true,
+        precise: false,
+        name: name_of(&dfs_swaps_1x2_retrying),
+    }));
+}
+
+#[test]
+fn sim_swaps_3x2_retrying() {
+    sim(
+        &build_model(SwapsCfg {
+            values: &[101, 103, 107],
+            combos: &[(0, 1), (1, 2), (0, 2)],
+            txns_per_cohort: 2,
+            extents: &[6],
+            retrying: true,
+            precise: false,
+            name: name_of(&sim_swaps_3x2_retrying),
+        }),
+        80,
+    );
+}
+
+#[test]
+fn sim_swaps_4x2_asymmetric_retrying() {
+    sim(
+        &build_model(SwapsCfg {
+            values: &[101, 103, 107, 111],
+            combos: &[(0, 1), (1, 2), (2, 3)],
+            txns_per_cohort: 2,
+            extents: &[1, 2],
+            retrying: true,
+            precise: false,
+            name: name_of(&sim_swaps_4x2_asymmetric_retrying),
+        }),
+        160,
+    );
+}
+
+#[test]
+fn sim_swaps_4x2_asymmetric_precise() {
+    sim(
+        &build_model(SwapsCfg {
+            values: &[101, 103, 107, 111],
+            combos: &[(0, 1), (1, 2), (2, 3)],
+            txns_per_cohort: 2,
+            extents: &[1, 2],
+            retrying: false,
+            precise: true,
+            name: name_of(&sim_swaps_4x2_asymmetric_precise),
+        }),
+        160,
+    );
+}
+
+#[test]
+fn sim_swaps_4x3_3x_precise() {
+    sim(
+        &build_model(SwapsCfg {
+            values: &[101, 103, 107, 111],
+            combos: &[(0, 1), (1, 2), (2, 3)],
+            txns_per_cohort: 3,
+            extents: &[1, 3, 8],
+            retrying: false,
+            precise: true,
+            name: name_of(&sim_swaps_4x3_3x_precise),
+        }),
+        240,
+    );
+}